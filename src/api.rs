@@ -0,0 +1,769 @@
+//! Async API for working with BOS components stored in SocialDB.
+//!
+//! The typical deploy flow is:
+//!
+//! 1. [`load_local_components`] reads the components from a local source folder;
+//! 2. [`fetch_remote_components`] fetches the deployed versions of the same components;
+//! 3. [`plan_deploy`] compares them and tells which components are new, updated or unchanged;
+//! 4. [`build_set_function_call`] builds the `set` function call to be signed by the caller.
+//!
+//! [`DeployPlanFile`] captures the result of steps 2-4 in a reviewable file that can be executed
+//! later, once it is verified that the deployed components have not changed in the meantime.
+use std::collections::{BTreeMap, HashMap};
+
+use color_eyre::eyre::{ContextCompat, WrapErr};
+use futures::StreamExt;
+use glob::glob;
+use near_cli_rs::common::{CallResultExt, RpcQueryResponseExt};
+use rand::Rng;
+
+pub use crate::socialdb_types::{ComponentName, SocialDbComponent};
+
+/// Components keyed by their name (folders are joined with `.`, e.g. `Common.Button`).
+pub type Components = HashMap<ComponentName, SocialDbComponent>;
+
+/// Controls how [`fetch_remote_components`] splits the requests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FetchOptions {
+    /// The number of components fetched with a single `get` call.
+    pub chunk_size: usize,
+    /// The number of `get` calls running at the same time.
+    pub concurrency: usize,
+    /// How many times a `get` call is retried after a transient RPC error (e.g. a timeout or 429).
+    pub max_retries: u32,
+    /// The delay before the first retry; it doubles with every next attempt (plus random jitter).
+    pub initial_backoff: std::time::Duration,
+}
+
+impl Default for FetchOptions {
+    fn default() -> Self {
+        Self {
+            chunk_size: 15,
+            concurrency: 10,
+            max_retries: 3,
+            initial_backoff: std::time::Duration::from_millis(500),
+        }
+    }
+}
+
+/// Arguments of the SocialDB `set` function call.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SetFunctionArgs {
+    pub data: crate::socialdb_types::SocialDb,
+}
+
+/// Loads all `*.jsx` components (and their optional `*.metadata.json` files) from `src_dir`.
+pub fn load_local_components(
+    src_dir: impl AsRef<std::path::Path>,
+) -> color_eyre::eyre::Result<Components> {
+    let src_dir = src_dir.as_ref();
+    let pattern = src_dir.join("**").join("*.jsx");
+    let pattern = pattern
+        .to_str()
+        .wrap_err_with(|| format!("The path {} is not valid UTF-8", src_dir.display()))?;
+
+    let mut components = HashMap::new();
+
+    for component_filepath in glob(pattern)?.filter_map(Result::ok) {
+        let component_name: ComponentName = component_filepath
+            .strip_prefix(src_dir)?
+            .with_extension("")
+            .components()
+            .filter_map(|component| match component {
+                std::path::Component::Normal(text) => text.to_str(),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+            .join(".");
+
+        let code = std::fs::read_to_string(&component_filepath).wrap_err_with(|| {
+            format!(
+                "Failed to read component source code from {}",
+                component_filepath.display()
+            )
+        })?;
+
+        let metadata_filepath = component_filepath.with_extension("metadata.json");
+        let metadata = if let Ok(metadata_json) = std::fs::read_to_string(&metadata_filepath) {
+            Some(serde_json::from_str(&metadata_json).wrap_err_with(|| {
+                format!(
+                    "Failed to parse component metadata from {}",
+                    metadata_filepath.display()
+                )
+            })?)
+        } else {
+            None
+        };
+
+        components.insert(
+            component_name,
+            SocialDbComponent::CodeWithMetadata { code, metadata },
+        );
+    }
+    Ok(components)
+}
+
+/// Fetches the given components of `account_id` stored under `social_db_folder`.
+///
+/// Components that do not exist on-chain are omitted from the result. Transient RPC errors are
+/// retried (see [`FetchOptions`]); if a batch still fails, its components are fetched one by one,
+/// and the error lists every component that could not be fetched. The responses are cached if the
+/// `cache_policy` allows it (see [`crate::cache`]).
+pub async fn fetch_remote_components(
+    rpc_endpoints: &crate::rpc::RpcEndpoints,
+    near_social_account_id: &near_primitives::types::AccountId,
+    account_id: &near_primitives::types::AccountId,
+    social_db_folder: &str,
+    component_names: &[ComponentName],
+    fetch_options: FetchOptions,
+    cache_policy: crate::cache::CachePolicy,
+) -> color_eyre::eyre::Result<Components> {
+    if fetch_options.chunk_size == 0 || fetch_options.concurrency == 0 {
+        color_eyre::eyre::bail!("The fetch chunk size and concurrency must be greater than zero");
+    }
+    // The batches are owned, so the fetching future stays `Send` (see `common::block_on`)
+    let batch_results = futures::stream::iter(
+        component_names
+            .chunks(fetch_options.chunk_size)
+            .map(<[ComponentName]>::to_vec),
+    )
+    .map(|components_name_batch| async move {
+        get_components_batch(
+            rpc_endpoints,
+            near_social_account_id,
+            account_id,
+            &components_name_batch,
+            social_db_folder,
+            fetch_options,
+            cache_policy,
+        )
+        .await
+    })
+    .buffer_unordered(fetch_options.concurrency)
+    .collect::<Vec<_>>()
+    .await;
+
+    let mut components = HashMap::new();
+    let mut failed_components = Vec::new();
+    for (batch_components, batch_failed_components) in batch_results {
+        components.extend(batch_components);
+        failed_components.extend(batch_failed_components);
+    }
+    if failed_components.is_empty() {
+        return Ok(components);
+    }
+
+    failed_components.sort_by(|(name_a, _), (name_b, _)| name_a.cmp(name_b));
+    let summary = failed_components
+        .iter()
+        .map(|(component_name, err)| format!(" - {component_name}: {err:#}"))
+        .collect::<Vec<_>>()
+        .join("\n");
+    color_eyre::eyre::bail!(
+        "Failed to fetch {} of {} components from SocialDB:\n{summary}",
+        failed_components.len(),
+        component_names.len()
+    )
+}
+
+/// Fetches a batch of components, falling back to fetching them one by one if the batch fails.
+///
+/// Returns the fetched components and the components that could not be fetched.
+async fn get_components_batch(
+    rpc_endpoints: &crate::rpc::RpcEndpoints,
+    near_social_account_id: &near_primitives::types::AccountId,
+    account_id: &near_primitives::types::AccountId,
+    components_names_batch: &[ComponentName],
+    social_db_folder: &str,
+    fetch_options: FetchOptions,
+    cache_policy: crate::cache::CachePolicy,
+) -> (Components, Vec<(ComponentName, color_eyre::eyre::Report)>) {
+    let batch_err = match get_components_with_retries(
+        rpc_endpoints,
+        near_social_account_id,
+        account_id,
+        components_names_batch,
+        social_db_folder,
+        fetch_options,
+        cache_policy,
+    )
+    .await
+    {
+        Ok(components) => return (components, vec![]),
+        Err(err) => err,
+    };
+    if let [component_name] = components_names_batch {
+        return (HashMap::new(), vec![(component_name.clone(), batch_err)]);
+    }
+
+    tracing::info!(
+        target: "near_teach_me",
+        parent: &tracing::Span::none(),
+        "Failed to fetch a batch of {} components, fetching them one by one:\n{}",
+        components_names_batch.len(),
+        near_cli_rs::common::indent_payload(&format!("{batch_err:#}"))
+    );
+    let mut components = HashMap::new();
+    let mut failed_components = Vec::new();
+    for component_name in components_names_batch {
+        match get_components_with_retries(
+            rpc_endpoints,
+            near_social_account_id,
+            account_id,
+            std::slice::from_ref(component_name),
+            social_db_folder,
+            fetch_options,
+            cache_policy,
+        )
+        .await
+        {
+            Ok(component) => components.extend(component),
+            Err(err) => failed_components.push((component_name.clone(), err)),
+        }
+    }
+    (components, failed_components)
+}
+
+async fn get_components_with_retries(
+    rpc_endpoints: &crate::rpc::RpcEndpoints,
+    near_social_account_id: &near_primitives::types::AccountId,
+    account_id: &near_primitives::types::AccountId,
+    components_names_batch: &[ComponentName],
+    social_db_folder: &str,
+    fetch_options: FetchOptions,
+    cache_policy: crate::cache::CachePolicy,
+) -> color_eyre::eyre::Result<Components> {
+    let mut attempt = 0;
+    loop {
+        match get_components(
+            rpc_endpoints,
+            near_social_account_id,
+            account_id,
+            components_names_batch,
+            social_db_folder,
+            cache_policy,
+        )
+        .await
+        {
+            Err(err) if attempt < fetch_options.max_retries && is_transient_error(&err) => {
+                let delay = backoff_delay(fetch_options.initial_backoff, attempt);
+                attempt += 1;
+                tracing::info!(
+                    target: "near_teach_me",
+                    parent: &tracing::Span::none(),
+                    "The RPC call failed with a transient error, retrying in {delay:?} (attempt {attempt} of {})",
+                    fetch_options.max_retries
+                );
+                tokio::time::sleep(delay).await;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Transport errors, rate limiting (429), timeouts and internal server errors are worth retrying;
+/// handler and request validation errors are not.
+fn is_transient_error(err: &color_eyre::eyre::Report) -> bool {
+    err.chain().any(|cause| {
+        cause.is::<crate::rpc::RpcTimeoutError>()
+            || cause
+                .downcast_ref::<near_jsonrpc_client::errors::JsonRpcError<
+                    near_jsonrpc_primitives::types::query::RpcQueryError,
+                >>()
+                .is_some_and(crate::rpc::is_endpoint_failure)
+    })
+}
+
+/// Exponential backoff with jitter: a random delay between 50% and 100% of `initial_backoff * 2^attempt`.
+fn backoff_delay(initial_backoff: std::time::Duration, attempt: u32) -> std::time::Duration {
+    let backoff = initial_backoff.saturating_mul(2u32.saturating_pow(attempt));
+    backoff.mul_f64(rand::thread_rng().gen_range(0.5..=1.0))
+}
+
+/// The result of comparing local components with the deployed ones.
+#[derive(Debug, Clone, Default)]
+pub struct DeployPlan {
+    /// Components that are not deployed yet.
+    pub new: Components,
+    /// Components whose code or metadata differs from the deployed version.
+    pub updated: Components,
+    /// Components that are deployed as is.
+    pub unchanged: Components,
+}
+
+impl DeployPlan {
+    /// Returns `true` if there is nothing to deploy.
+    pub fn is_empty(&self) -> bool {
+        self.new.is_empty() && self.updated.is_empty()
+    }
+
+    /// Returns the new and updated components.
+    pub fn into_components_to_deploy(self) -> Components {
+        self.new.into_iter().chain(self.updated).collect()
+    }
+}
+
+/// Returns `true` if `new_component` has to be deployed over `old_component`.
+///
+/// Leading and trailing whitespace in the code is ignored, and missing local metadata does not
+/// count as a change, so the deployed metadata is kept.
+pub fn has_component_changed(
+    old_component: &SocialDbComponent,
+    new_component: &SocialDbComponent,
+) -> bool {
+    old_component.code().trim() != new_component.code().trim()
+        || (new_component.metadata().is_some()
+            && old_component.metadata() != new_component.metadata())
+}
+
+/// Splits the local components into new, updated and unchanged ones.
+pub fn plan_deploy(local_components: Components, remote_components: &Components) -> DeployPlan {
+    let mut plan = DeployPlan::default();
+    for (component_name, component) in local_components {
+        match remote_components.get(&component_name) {
+            None => plan.new.insert(component_name, component),
+            Some(remote_component) if has_component_changed(remote_component, &component) => {
+                plan.updated.insert(component_name, component)
+            }
+            Some(_) => plan.unchanged.insert(component_name, component),
+        };
+    }
+    plan
+}
+
+/// Wraps the components into the SocialDB data structure (`<account_id>/<social_db_folder>/<component>`).
+pub fn social_db_data(
+    account_id: &near_primitives::types::AccountId,
+    social_db_folder: &str,
+    components: Components,
+) -> crate::socialdb_types::SocialDb {
+    crate::socialdb_types::SocialDb {
+        accounts: HashMap::from([(
+            account_id.clone(),
+            crate::socialdb_types::SocialDbComponentKey {
+                key: HashMap::from([(
+                    social_db_folder.to_string(),
+                    crate::socialdb_types::SocialDbAccountMetadata { components },
+                )]),
+            },
+        )]),
+    }
+}
+
+/// Builds the SocialDB `set` function call that deploys `components_to_deploy`.
+///
+/// The attached deposit covers the storage required on top of `remote_components`.
+pub async fn build_set_function_call(
+    json_rpc_client: &near_jsonrpc_client::JsonRpcClient,
+    near_social_account_id: &near_primitives::types::AccountId,
+    account_id: &near_primitives::types::AccountId,
+    social_db_folder: &str,
+    components_to_deploy: Components,
+    remote_components: Components,
+) -> color_eyre::eyre::Result<near_primitives::transaction::FunctionCallAction> {
+    let new_social_db_state = social_db_data(account_id, social_db_folder, components_to_deploy);
+    let new_social_db_state_json = serde_json::json!(&new_social_db_state);
+    let remote_social_db_state_json = serde_json::json!(&social_db_data(
+        account_id,
+        social_db_folder,
+        remote_components
+    ));
+
+    let args = serde_json::to_string(&SetFunctionArgs {
+        data: new_social_db_state,
+    })?
+    .into_bytes();
+
+    let deposit = near_socialdb_client::required_deposit(
+        json_rpc_client,
+        near_social_account_id,
+        account_id,
+        &new_social_db_state_json,
+        Some(&remote_social_db_state_json),
+    )
+    .await?;
+
+    Ok(near_primitives::transaction::FunctionCallAction {
+        method_name: "set".to_string(),
+        args,
+        gas: near_cli_rs::common::NearGas::from_tgas(300).as_gas(),
+        deposit: deposit.as_yoctonear(),
+    })
+}
+
+/// The SHA-256 hash of the component code and metadata (as they are stored in SocialDB).
+pub fn component_hash(component: &SocialDbComponent) -> String {
+    use sha2::Digest;
+
+    let component = serde_json::to_value(component)
+        .expect("SocialDB component is not expected to fail on serialization");
+    format!("{:x}", sha2::Sha256::digest(component.to_string()))
+}
+
+/// The unified diff of the local component code against the deployed version (or against an
+/// empty file for a new component).
+pub fn component_diff(
+    component_name: &str,
+    remote_component: Option<&SocialDbComponent>,
+    local_component: &SocialDbComponent,
+) -> String {
+    similar::TextDiff::from_lines(
+        remote_component.map_or("", |remote_component| remote_component.code()),
+        local_component.code(),
+    )
+    .unified_diff()
+    .header(
+        &format!("remote/{component_name}"),
+        &format!("local/{component_name}"),
+    )
+    .to_string()
+}
+
+/// Whether a planned component is deployed for the first time or replaces the deployed version.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ComponentChange {
+    New,
+    Updated,
+}
+
+/// A component in a [`DeployPlanFile`].
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct PlannedComponent {
+    pub change: ComponentChange,
+    /// The [`component_hash`] of the component to deploy.
+    pub local_hash: String,
+    /// The [`component_hash`] of the deployed component when the plan was made.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remote_hash: Option<String>,
+    /// The unified diff of the code against the deployed version.
+    pub diff: String,
+}
+
+/// A deploy plan that can be reviewed before it is applied (e.g. by the other signers of a
+/// multisig account).
+///
+/// Apart from the block it was made at (and the storage deposit, which depends on the storage
+/// balance of the account), the same local and remote components produce the same plan, so the
+/// reviewed `components` and `set_args` can be compared across plans.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct DeployPlanFile {
+    pub network: String,
+    pub near_social_account_id: near_primitives::types::AccountId,
+    pub account_id: near_primitives::types::AccountId,
+    pub social_db_folder: String,
+    /// The final block when the plan was made.
+    pub block_height: near_primitives::types::BlockHeight,
+    pub block_hash: near_primitives::hash::CryptoHash,
+    pub components: BTreeMap<ComponentName, PlannedComponent>,
+    /// The arguments of the SocialDB `set` call.
+    pub set_args: serde_json::Value,
+    /// The storage deposit (in yoctoNEAR) required by the `set` call.
+    pub deposit: near_cli_rs::types::near_token::NearToken,
+}
+
+impl DeployPlanFile {
+    /// Plans the deployment of the new and updated `local_components`, or returns `None` if
+    /// there is nothing to deploy.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn new(
+        json_rpc_client: &near_jsonrpc_client::JsonRpcClient,
+        network: &str,
+        near_social_account_id: &near_primitives::types::AccountId,
+        account_id: &near_primitives::types::AccountId,
+        social_db_folder: &str,
+        block: (
+            near_primitives::types::BlockHeight,
+            near_primitives::hash::CryptoHash,
+        ),
+        local_components: Components,
+        remote_components: Components,
+    ) -> color_eyre::eyre::Result<Option<Self>> {
+        let components_to_deploy =
+            plan_deploy(local_components, &remote_components).into_components_to_deploy();
+        if components_to_deploy.is_empty() {
+            return Ok(None);
+        }
+        let components = components_to_deploy
+            .iter()
+            .map(|(component_name, component)| {
+                let remote_component = remote_components.get(component_name);
+                let diff = component_diff(component_name, remote_component, component);
+                let planned_component = PlannedComponent {
+                    change: if remote_component.is_some() {
+                        ComponentChange::Updated
+                    } else {
+                        ComponentChange::New
+                    },
+                    local_hash: component_hash(component),
+                    remote_hash: remote_component.map(component_hash),
+                    diff,
+                };
+                (component_name.clone(), planned_component)
+            })
+            .collect();
+        let set_function_call = build_set_function_call(
+            json_rpc_client,
+            near_social_account_id,
+            account_id,
+            social_db_folder,
+            components_to_deploy,
+            remote_components,
+        )
+        .await?;
+        Ok(Some(Self {
+            network: network.to_owned(),
+            near_social_account_id: near_social_account_id.clone(),
+            account_id: account_id.clone(),
+            social_db_folder: social_db_folder.to_owned(),
+            block_height: block.0,
+            block_hash: block.1,
+            components,
+            set_args: serde_json::from_slice(&set_function_call.args)?,
+            deposit: near_cli_rs::types::near_token::NearToken::from_yoctonear(
+                set_function_call.deposit,
+            ),
+        }))
+    }
+
+    /// Checks that the deployed components are still the ones the plan was made against.
+    pub fn verify_remote_components(
+        &self,
+        remote_components: &Components,
+    ) -> color_eyre::eyre::Result<()> {
+        let changed_components = self
+            .components
+            .iter()
+            .filter(|(component_name, planned_component)| {
+                remote_components.get(*component_name).map(component_hash)
+                    != planned_component.remote_hash
+            })
+            .map(|(component_name, _)| component_name.as_str())
+            .collect::<Vec<_>>();
+        if !changed_components.is_empty() {
+            color_eyre::eyre::bail!(
+                "The deployed components have changed since the plan was made at block #{} ({}): {}. Make a new plan.",
+                self.block_height,
+                self.block_hash,
+                changed_components.join(", ")
+            );
+        }
+        Ok(())
+    }
+
+    /// Checks that the `set` arguments deploy exactly the planned components and builds the call.
+    pub fn set_function_call(
+        &self,
+    ) -> color_eyre::eyre::Result<near_primitives::transaction::FunctionCallAction> {
+        let set_args: SetFunctionArgs = serde_json::from_value(self.set_args.clone())
+            .wrap_err("The `set_args` of the plan are not SocialDB `set` arguments")?;
+        let components = components_from_social_db(
+            set_args.data.clone(),
+            &self.account_id,
+            &self.social_db_folder,
+        );
+        let is_consistent = components.len() == self.components.len()
+            && components.iter().all(|(component_name, component)| {
+                self.components
+                    .get(component_name)
+                    .is_some_and(|planned_component| {
+                        planned_component.local_hash == component_hash(component)
+                    })
+            });
+        if !is_consistent {
+            color_eyre::eyre::bail!(
+                "The `set_args` of the plan do not match the hashes of the planned components"
+            );
+        }
+        Ok(near_primitives::transaction::FunctionCallAction {
+            method_name: "set".to_string(),
+            args: serde_json::to_vec(&set_args)?,
+            gas: near_cli_rs::common::NearGas::from_tgas(300).as_gas(),
+            deposit: self.deposit.as_yoctonear(),
+        })
+    }
+}
+
+#[tracing::instrument(name = "Obtaining remote components ...", skip_all)]
+async fn get_components(
+    rpc_endpoints: &crate::rpc::RpcEndpoints,
+    near_social_account_id: &near_primitives::types::AccountId,
+    account_id: &near_primitives::types::AccountId,
+    components_names_batch: &[ComponentName],
+    social_db_folder: &str,
+    cache_policy: crate::cache::CachePolicy,
+) -> color_eyre::Result<Components> {
+    let args = serde_json::to_string(&crate::socialdb_types::SocialDbQuery {
+        keys: components_names_batch
+            .iter()
+            .map(|name| format!("{account_id}/{social_db_folder}/{name}/**"))
+            .collect(),
+        options: None,
+    })
+    .wrap_err("Internal error: could not serialize SocialDB input args")?
+    .into_bytes();
+
+    let block_reference = near_primitives::types::Finality::Final.into();
+    let cache_key = crate::cache::CacheKey {
+        network: rpc_endpoints.network(),
+        contract_account_id: near_social_account_id,
+        method_name: "get",
+        args: &args,
+        block_reference: &block_reference,
+    };
+    if let Some(result) = crate::cache::get(&cache_key, cache_policy) {
+        return components_from_call_result(
+            near_primitives::views::CallResult {
+                result,
+                logs: vec![],
+            },
+            account_id,
+            social_db_folder,
+        );
+    }
+
+    let query_view_method_request = near_jsonrpc_client::methods::query::RpcQueryRequest {
+        block_reference: block_reference.clone(),
+        request: near_primitives::views::QueryRequest::CallFunction {
+            account_id: near_social_account_id.clone(),
+            method_name: "get".to_string(),
+            args: near_primitives::types::FunctionArgs::from(args.clone()),
+        },
+    };
+
+    tracing::info!(
+        target: "near_teach_me",
+        parent: &tracing::Span::none(),
+        "I am making HTTP call to NEAR JSON RPC to call a read-only function 'get' of the <{}> contract, learn more https://docs.near.org/api/rpc/contracts#call-a-contract-function",
+        near_social_account_id
+    );
+
+    if let Ok(request_payload) = near_jsonrpc_client::methods::to_json(&query_view_method_request) {
+        tracing::info!(
+            target: "near_teach_me",
+            parent: &tracing::Span::none(),
+            "HTTP POST {}",
+            rpc_endpoints.json_rpc_client().server_addr()
+        );
+        tracing::info!(
+            target: "near_teach_me",
+            parent: &tracing::Span::none(),
+            "JSON Request Body:\n{}",
+            near_cli_rs::common::indent_payload(&format!("{:#}", request_payload))
+        );
+    }
+
+    let query_view_method_response = rpc_endpoints
+        .call(&query_view_method_request)
+        .await
+        .inspect_err(|err| match err.downcast_ref::<near_jsonrpc_client::errors::JsonRpcError<
+            near_jsonrpc_primitives::types::query::RpcQueryError,
+        >>() {
+            Some(near_jsonrpc_client::errors::JsonRpcError::TransportError(transport_error)) => {
+                tracing::info!(
+                    target: "near_teach_me",
+                    parent: &tracing::Span::none(),
+                    "JSON RPC Request failed due to connectivity issue:\n{}",
+                    near_cli_rs::common::indent_payload(&format!("{:#?}", transport_error))
+                );
+            }
+            Some(near_jsonrpc_client::errors::JsonRpcError::ServerError(
+                near_jsonrpc_client::errors::JsonRpcServerError::HandlerError(handler_error),
+            )) => {
+                tracing::info!(
+                    target: "near_teach_me",
+                    parent: &tracing::Span::none(),
+                    "JSON RPC Request returned a handling error:\n{}",
+                    near_cli_rs::common::indent_payload(&serde_json::to_string_pretty(handler_error).unwrap_or_else(|_| handler_error.to_string()))
+                );
+            }
+            Some(near_jsonrpc_client::errors::JsonRpcError::ServerError(server_error)) => {
+                tracing::info!(
+                    target: "near_teach_me",
+                    parent: &tracing::Span::none(),
+                    "JSON RPC Request returned a generic server error:\n{}",
+                    near_cli_rs::common::indent_payload(&format!("{:#?}", server_error))
+                );
+            }
+            None => {
+                tracing::info!(
+                    target: "near_teach_me",
+                    parent: &tracing::Span::none(),
+                    "JSON RPC Request failed:\n{}",
+                    near_cli_rs::common::indent_payload(&format!("{err:#}"))
+                );
+            }
+        })
+        .wrap_err("Failed to query batch of components from Social DB")?;
+
+    query_view_method_response
+        .call_result()
+        .inspect(|call_result| {
+            tracing::info!(
+                target: "near_teach_me",
+                parent: &tracing::Span::none(),
+                "JSON RPC Response:\n{}",
+                near_cli_rs::common::indent_payload(&format!(
+                    "{{\n  \"block_hash\": {}\n  \"block_height\": {}\n  \"logs\": {:?}\n  \"result\": {:?}\n}}",
+                    query_view_method_response.block_hash,
+                    query_view_method_response.block_height,
+                    call_result.logs,
+                    call_result.result
+                ))
+            );
+            tracing::info!(
+                target: "near_teach_me",
+                parent: &tracing::Span::none(),
+                "Decoding the \"result\" array of bytes as UTF-8 string (tip: you can use this Python snippet to do it: `\"\".join([chr(c) for c in result])`):\n{}\n",
+                near_cli_rs::common::indent_payload(
+                    &String::from_utf8(call_result.result.clone())
+                        .unwrap_or_else(|_| "<decoding failed - the result is not a UTF-8 string>".to_owned())
+                )
+            );
+        })
+        .inspect_err(|_| {
+            tracing::info!(
+                target: "near_teach_me",
+                parent: &tracing::Span::none(),
+                "JSON RPC Response:\n{}",
+                near_cli_rs::common::indent_payload("Internal error: Received unexpected query kind in response to a view-function query call")
+            );
+        })?;
+
+    match query_view_method_response.kind {
+        near_jsonrpc_primitives::types::query::QueryResponseKind::CallResult(call_result) => {
+            crate::cache::put(&cache_key, &call_result.result, cache_policy);
+            components_from_call_result(call_result, account_id, social_db_folder)
+        }
+        _ => unreachable!("ERROR: unexpected response type from JSON RPC client"),
+    }
+}
+
+fn components_from_call_result(
+    call_result: near_primitives::views::CallResult,
+    account_id: &near_primitives::types::AccountId,
+    social_db_folder: &str,
+) -> color_eyre::Result<Components> {
+    Ok(components_from_social_db(
+        call_result
+            .parse_result_from_json::<crate::socialdb_types::SocialDb>()
+            .wrap_err("ERROR: failed to parse Social DB response")?,
+        account_id,
+        social_db_folder,
+    ))
+}
+
+fn components_from_social_db(
+    mut social_db: crate::socialdb_types::SocialDb,
+    account_id: &near_primitives::types::AccountId,
+    social_db_folder: &str,
+) -> Components {
+    social_db
+        .accounts
+        .remove(account_id)
+        .map(|crate::socialdb_types::SocialDbComponentKey { key }| key)
+        .unwrap_or_default()
+        .remove(social_db_folder)
+        .map(|crate::socialdb_types::SocialDbAccountMetadata { components }| components)
+        .unwrap_or_default()
+}
@@ -0,0 +1,214 @@
+//! On-disk cache for SocialDB view calls.
+//!
+//! Responses are keyed by network, contract, method, arguments (i.e. the key patterns) and block
+//! reference. Responses at a specific block never change, so they are cached indefinitely, while
+//! responses at the final (or any other moving) block expire after [`FINAL_BLOCK_TTL`].
+//!
+//! The cache is opt-in per call site with [`CachePolicy`], so only read-only commands use it, and
+//! `--no-cache` turns it off for the whole run. It is stored in `$BOS_CACHE_DIR` (or in the
+//! `bos-cli` folder of the user cache directory), and the expired entries are pruned the first
+//! time the cache is used in a run.
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use color_eyre::eyre::{ContextCompat, WrapErr};
+use sha2::Digest;
+
+/// How long the responses at the final block are reused.
+pub const FINAL_BLOCK_TTL: std::time::Duration = std::time::Duration::from_secs(5 * 60);
+
+static DISABLED: AtomicBool = AtomicBool::new(false);
+
+static PRUNE_EXPIRED_ENTRIES: std::sync::Once = std::sync::Once::new();
+
+/// Whether a view call may be answered from the cache.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CachePolicy {
+    /// Reuse the cached response and cache the fresh one (read-only commands, e.g. `data view`).
+    Use,
+    /// Always call the RPC endpoint (e.g. when the response is used to build a transaction).
+    Bypass,
+}
+
+impl CachePolicy {
+    fn is_used(self) -> bool {
+        self == Self::Use && !DISABLED.load(Ordering::Relaxed)
+    }
+}
+
+/// Turns the cache off for the rest of the run, whatever the call sites ask for (`--no-cache`).
+pub fn disable() {
+    DISABLED.store(true, Ordering::Relaxed);
+}
+
+/// Identifies a view function call.
+pub struct CacheKey<'a> {
+    pub network: &'a str,
+    pub contract_account_id: &'a near_primitives::types::AccountId,
+    pub method_name: &'a str,
+    pub args: &'a [u8],
+    pub block_reference: &'a near_primitives::types::BlockReference,
+}
+
+impl CacheKey<'_> {
+    fn file_name(&self) -> color_eyre::eyre::Result<String> {
+        let mut hasher = sha2::Sha256::new();
+        for part in [
+            self.network.as_bytes(),
+            self.contract_account_id.as_bytes(),
+            self.method_name.as_bytes(),
+            self.args,
+            serde_json::to_string(self.block_reference)?.as_bytes(),
+        ] {
+            hasher.update((part.len() as u64).to_le_bytes());
+            hasher.update(part);
+        }
+        Ok(format!("{:x}.json", hasher.finalize()))
+    }
+
+    fn ttl(&self) -> Option<std::time::Duration> {
+        match self.block_reference {
+            near_primitives::types::BlockReference::BlockId(_) => None,
+            _ => Some(FINAL_BLOCK_TTL),
+        }
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+struct CacheEntry {
+    /// Seconds since the UNIX epoch.
+    created_at: u64,
+    /// The response is at a moving block (e.g. the final one), so it expires.
+    expires: bool,
+    result: String,
+}
+
+/// The cache directory: `$BOS_CACHE_DIR` or `<user cache directory>/bos-cli`.
+pub fn cache_dir() -> color_eyre::eyre::Result<std::path::PathBuf> {
+    if let Some(cache_dir) = std::env::var_os("BOS_CACHE_DIR") {
+        return Ok(cache_dir.into());
+    }
+    Ok(dirs::cache_dir()
+        .wrap_err("Failed to determine the user cache directory")?
+        .join("bos-cli"))
+}
+
+fn now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs())
+}
+
+/// Returns the cached result of the view call, if the policy allows it and the entry is fresh.
+pub fn get(key: &CacheKey, policy: CachePolicy) -> Option<Vec<u8>> {
+    if !policy.is_used() {
+        return None;
+    }
+    prune_expired_entries_once();
+    let path = cache_dir().ok()?.join(key.file_name().ok()?);
+    let entry: CacheEntry = serde_json::from_slice(&std::fs::read(path).ok()?).ok()?;
+    if let Some(ttl) = key.ttl() {
+        if now().saturating_sub(entry.created_at) > ttl.as_secs() {
+            return None;
+        }
+    }
+    tracing::info!(
+        target: "near_teach_me",
+        parent: &tracing::Span::none(),
+        "Using the cached response of '{}' of the <{}> contract (use --no-cache to bypass the cache)",
+        key.method_name,
+        key.contract_account_id
+    );
+    Some(entry.result.into_bytes())
+}
+
+/// Stores the result of the view call if the policy allows it.
+///
+/// Failing to write the cache is not an error, the result is just not cached.
+pub fn put(key: &CacheKey, result: &[u8], policy: CachePolicy) {
+    if !policy.is_used() {
+        return;
+    }
+    prune_expired_entries_once();
+    let Ok(result) = std::str::from_utf8(result) else {
+        return;
+    };
+    let write = || -> color_eyre::eyre::Result<()> {
+        let cache_dir = cache_dir()?;
+        std::fs::create_dir_all(&cache_dir)?;
+        std::fs::write(
+            cache_dir.join(key.file_name()?),
+            serde_json::to_vec(&CacheEntry {
+                created_at: now(),
+                expires: key.ttl().is_some(),
+                result: result.to_owned(),
+            })?,
+        )?;
+        Ok(())
+    };
+    if let Err(err) = write() {
+        tracing::warn!("Failed to write the SocialDB response cache: {err:#}");
+    }
+}
+
+/// Removes all the cached responses and returns the number of removed entries.
+pub fn clear() -> color_eyre::eyre::Result<usize> {
+    remove_entries(|_| true)
+}
+
+/// Removes the expired (and unreadable) cached responses and returns the number of removed entries.
+pub fn prune_expired_entries() -> color_eyre::eyre::Result<usize> {
+    let now = now();
+    remove_entries(|path| {
+        read_entry(path).is_none_or(|entry| {
+            entry.expires && now.saturating_sub(entry.created_at) > FINAL_BLOCK_TTL.as_secs()
+        })
+    })
+}
+
+/// Prunes the expired entries the first time the cache is used in the run.
+fn prune_expired_entries_once() {
+    PRUNE_EXPIRED_ENTRIES.call_once(|| {
+        if let Err(err) = prune_expired_entries() {
+            tracing::warn!("Failed to prune the SocialDB response cache: {err:#}");
+        }
+    });
+}
+
+/// Removes the cached responses at the final block, so the data changed by a transaction is not
+/// served from the cache.
+pub fn invalidate_final_block_entries() {
+    if let Err(err) = remove_entries(|path| read_entry(path).is_none_or(|entry| entry.expires)) {
+        tracing::warn!("Failed to invalidate the SocialDB response cache: {err:#}");
+    }
+}
+
+fn read_entry(path: &std::path::Path) -> Option<CacheEntry> {
+    std::fs::read(path)
+        .ok()
+        .and_then(|entry| serde_json::from_slice(&entry).ok())
+}
+
+fn remove_entries(
+    should_remove: impl Fn(&std::path::Path) -> bool,
+) -> color_eyre::eyre::Result<usize> {
+    let cache_dir = cache_dir()?;
+    if !cache_dir.exists() {
+        return Ok(0);
+    }
+    let mut removed_entries = 0;
+    for entry in std::fs::read_dir(&cache_dir)
+        .wrap_err_with(|| format!("Failed to read the cache directory {}", cache_dir.display()))?
+    {
+        let path = entry?.path();
+        if path
+            .extension()
+            .is_some_and(|extension| extension == "json")
+            && should_remove(&path)
+        {
+            std::fs::remove_file(&path)
+                .wrap_err_with(|| format!("Failed to remove {}", path.display()))?;
+            removed_entries += 1;
+        }
+    }
+    Ok(removed_entries)
+}
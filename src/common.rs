@@ -1,10 +1,6 @@
-use std::collections::HashMap;
-
-use color_eyre::eyre::WrapErr;
+use color_eyre::eyre::{ContextCompat, WrapErr};
 use console::{style, Style};
-use futures::StreamExt;
-use glob::glob;
-use near_cli_rs::common::{CallResultExt, RpcQueryResponseExt};
+use near_cli_rs::common::{CallResultExt, JsonRpcClientExt, RpcQueryResponseExt};
 use serde::de::{Deserialize, Deserializer};
 use similar::{ChangeTag, TextDiff};
 use tracing_indicatif::span_ext::IndicatifSpanExt;
@@ -20,6 +16,98 @@ impl std::fmt::Display for Line {
     }
 }
 
+/// Resolves the SocialDB contract account for the network: the `near_social_db_contract_account_id`
+/// from the network config takes precedence, and the built-in defaults are used as a fallback.
+pub fn get_near_social_account_id(
+    network_config: &near_cli_rs::config::NetworkConfig,
+) -> color_eyre::eyre::Result<near_primitives::types::AccountId> {
+    network_config
+        .near_social_db_contract_account_id
+        .clone()
+        .or_else(|| {
+            crate::consts::NEAR_SOCIAL_ACCOUNT_ID
+                .get(network_config.network_name.as_str())
+                .cloned()
+        })
+        .wrap_err_with(|| {
+            format!(
+                "The <{}> network does not have a near-social contract. Set `near_social_db_contract_account_id` for this network in the near CLI config or use `--social-db-contract`.",
+                network_config.network_name
+            )
+        })
+}
+
+lazy_static::lazy_static! {
+    static ref RUNTIME: tokio::runtime::Runtime = tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .expect("Failed to start the Tokio runtime");
+}
+
+/// Runs the future to completion from synchronous code (e.g. interactive_clap callbacks).
+///
+/// All the calls share a single runtime. When called from within a multi-thread Tokio runtime
+/// (e.g. when the CLI commands are embedded into async code), the current runtime is used instead
+/// of starting a nested one. A current-thread runtime cannot be blocked in place, so the future
+/// runs on the shared runtime from a separate thread instead.
+pub fn block_on<F>(future: F) -> F::Output
+where
+    F: std::future::Future + Send,
+    F::Output: Send,
+{
+    match tokio::runtime::Handle::try_current() {
+        Ok(handle) => match handle.runtime_flavor() {
+            tokio::runtime::RuntimeFlavor::CurrentThread => std::thread::scope(|scope| {
+                scope
+                    .spawn(|| RUNTIME.block_on(future))
+                    .join()
+                    .expect("The future panicked")
+            }),
+            _ => tokio::task::block_in_place(|| handle.block_on(future)),
+        },
+        Err(_) => RUNTIME.block_on(future),
+    }
+}
+
+/// Calls a view function like `JsonRpcClientExt::blocking_call_view_function`, but fails over
+/// to the fallback RPC endpoints of the network (see [`crate::rpc`]) and uses the response cache
+/// if the `cache_policy` allows it (see [`crate::cache`]).
+pub fn call_view_function_with_failover(
+    network_config: &near_cli_rs::config::NetworkConfig,
+    account_id: &near_primitives::types::AccountId,
+    method_name: &str,
+    args: Vec<u8>,
+    block_reference: near_primitives::types::BlockReference,
+    cache_policy: crate::cache::CachePolicy,
+) -> color_eyre::eyre::Result<near_primitives::views::CallResult> {
+    let cache_key = crate::cache::CacheKey {
+        network: &network_config.network_name,
+        contract_account_id: account_id,
+        method_name,
+        args: &args,
+        block_reference: &block_reference,
+    };
+    if let Some(result) = crate::cache::get(&cache_key, cache_policy) {
+        return Ok(near_primitives::views::CallResult {
+            result,
+            logs: vec![],
+        });
+    }
+    let call_result = block_on(crate::rpc::RpcEndpoints::for_network(network_config).call(
+        &near_jsonrpc_client::methods::query::RpcQueryRequest {
+            block_reference: block_reference.clone(),
+            request: near_primitives::views::QueryRequest::CallFunction {
+                account_id: account_id.clone(),
+                method_name: method_name.to_owned(),
+                args: near_primitives::types::FunctionArgs::from(args.clone()),
+            },
+        },
+    ))?
+    .call_result()?;
+    crate::cache::put(&cache_key, &call_result.result, cache_policy);
+    Ok(call_result)
+}
+
 pub struct DiffCodeError;
 
 pub fn diff_code(old_code: &str, new_code: &str) -> Result<(), DiffCodeError> {
@@ -28,13 +116,13 @@ pub fn diff_code(old_code: &str, new_code: &str) -> Result<(), DiffCodeError> {
     if old_code == new_code {
         return Ok(());
     }
-    println!();
+    crate::human_println!();
 
     let diff = TextDiff::from_lines(old_code, new_code);
 
     for (idx, group) in diff.grouped_ops(3).iter().enumerate() {
         if idx > 0 {
-            println!("{:-^1$}", "-", 80);
+            crate::human_println!("{:-^1$}", "-", 80);
         }
         for op in group {
             for change in diff.iter_inline_changes(op) {
@@ -43,7 +131,7 @@ pub fn diff_code(old_code: &str, new_code: &str) -> Result<(), DiffCodeError> {
                     ChangeTag::Insert => ("+", Style::new().green()),
                     ChangeTag::Equal => (" ", Style::new().dim()),
                 };
-                print!(
+                crate::human_print!(
                     "{}{} |{}",
                     style(Line(change.old_index())).dim(),
                     style(Line(change.new_index())).dim(),
@@ -51,13 +139,13 @@ pub fn diff_code(old_code: &str, new_code: &str) -> Result<(), DiffCodeError> {
                 );
                 for (emphasized, value) in change.iter_strings_lossy() {
                     if emphasized {
-                        print!("{}", s.apply_to(value).underlined().on_black());
+                        crate::human_print!("{}", s.apply_to(value).underlined().on_black());
                     } else {
-                        print!("{}", s.apply_to(value));
+                        crate::human_print!("{}", s.apply_to(value));
                     }
                 }
                 if change.missing_newline() {
-                    println!();
+                    crate::human_println!();
                 }
             }
         }
@@ -65,47 +153,19 @@ pub fn diff_code(old_code: &str, new_code: &str) -> Result<(), DiffCodeError> {
     Err(DiffCodeError)
 }
 
-pub fn get_local_components(
-) -> color_eyre::eyre::Result<HashMap<String, crate::socialdb_types::SocialDbComponent>> {
-    let mut components = HashMap::new();
-
-    for component_filepath in glob("./src/**/*.jsx")?.filter_map(Result::ok) {
-        let component_name: crate::socialdb_types::ComponentName = component_filepath
-            .strip_prefix("src")?
-            .with_extension("")
-            .components()
-            .filter_map(|component| match component {
-                std::path::Component::Normal(text) => text.to_str(),
-                _ => None,
-            })
-            .collect::<Vec<_>>()
-            .join(".");
-
-        let code = std::fs::read_to_string(&component_filepath).wrap_err_with(|| {
-            format!(
-                "Failed to read component source code from {}",
-                component_filepath.display()
-            )
-        })?;
-
-        let metadata_filepath = component_filepath.with_extension("metadata.json");
-        let metadata = if let Ok(metadata_json) = std::fs::read_to_string(&metadata_filepath) {
-            Some(serde_json::from_str(&metadata_json).wrap_err_with(|| {
-                format!(
-                    "Failed to parse component metadata from {}",
-                    metadata_filepath.display()
-                )
-            })?)
-        } else {
-            None
-        };
+/// Counts the inserted and deleted lines between two versions of the component code.
+pub fn count_changed_lines(old_code: &str, new_code: &str) -> (usize, usize) {
+    TextDiff::from_lines(old_code.trim(), new_code.trim())
+        .iter_all_changes()
+        .fold((0, 0), |(inserted, deleted), change| match change.tag() {
+            ChangeTag::Insert => (inserted + 1, deleted),
+            ChangeTag::Delete => (inserted, deleted + 1),
+            ChangeTag::Equal => (inserted, deleted),
+        })
+}
 
-        components.insert(
-            component_name,
-            crate::socialdb_types::SocialDbComponent::CodeWithMetadata { code, metadata },
-        );
-    }
-    Ok(components)
+pub fn get_local_components() -> color_eyre::eyre::Result<crate::api::Components> {
+    crate::api::load_local_components("src")
 }
 
 #[tracing::instrument(name = "Getting remote components for", skip_all)]
@@ -115,210 +175,120 @@ pub fn get_remote_components(
     near_social_account_id: &near_primitives::types::AccountId,
     account_id: &near_primitives::types::AccountId,
     social_db_folder: &str,
-) -> color_eyre::eyre::Result<
-    HashMap<crate::socialdb_types::ComponentName, crate::socialdb_types::SocialDbComponent>,
-> {
+    fetch_options: crate::api::FetchOptions,
+    cache_policy: crate::cache::CachePolicy,
+) -> color_eyre::eyre::Result<crate::api::Components> {
     tracing::Span::current().pb_set_message(&format!(
         "<{account_id}> from contract <{near_social_account_id}> on network <{}> ...",
         network_config.network_name
     ));
     tracing::info!(target: "near_teach_me", "<{account_id}> from contract <{near_social_account_id}> on network <{}> ...", network_config.network_name);
-    let runtime = tokio::runtime::Builder::new_multi_thread()
-        .enable_all()
-        .build()?;
-    let chunk_size = 15;
-    let concurrency = 10;
-
-    runtime
-        .block_on(
-            futures::stream::iter(component_name_list.chunks(chunk_size))
-                .map(|components_name_batch| async {
-                    get_components(
-                        network_config,
-                        near_social_account_id,
-                        account_id,
-                        components_name_batch,
-                        social_db_folder,
-                    )
-                    .await
-                })
-                .buffer_unordered(concurrency)
-                .collect::<Vec<Result<_, _>>>(),
-        )
-        .into_iter()
-        .try_fold(HashMap::new(), |mut acc, x| {
-            acc.extend(x?);
-            Ok::<_, color_eyre::eyre::Error>(acc)
-        })
-}
+    let component_name_list = component_name_list.into_iter().cloned().collect::<Vec<_>>();
 
-#[tracing::instrument(name = "Obtaining remote components ...", skip_all)]
-async fn get_components(
-    network_config: &near_cli_rs::config::NetworkConfig,
-    near_social_account_id: &near_primitives::types::AccountId,
-    account_id: &near_primitives::types::AccountId,
-    components_names_batch: &[&crate::socialdb_types::ComponentName],
-    social_db_folder: &str,
-) -> color_eyre::Result<
-    HashMap<crate::socialdb_types::ComponentName, crate::socialdb_types::SocialDbComponent>,
-> {
-    let args = serde_json::to_string(&crate::socialdb_types::SocialDbQuery {
-        keys: components_names_batch
-            .iter()
-            .map(|name| format!("{account_id}/{social_db_folder}/{name}/**"))
-            .collect(),
-    })
-    .wrap_err("Internal error: could not serialize SocialDB input args")?
-    .into_bytes();
-
-    let query_view_method_request = near_jsonrpc_client::methods::query::RpcQueryRequest {
-        block_reference: near_primitives::types::Finality::Final.into(),
-        request: near_primitives::views::QueryRequest::CallFunction {
-            account_id: near_social_account_id.clone(),
-            method_name: "get".to_string(),
-            args: near_primitives::types::FunctionArgs::from(args.clone()),
-        },
-    };
-
-    tracing::info!(
-        target: "near_teach_me",
-        parent: &tracing::Span::none(),
-        "I am making HTTP call to NEAR JSON RPC to call a read-only function 'get' of the <{}> contract, learn more https://docs.near.org/api/rpc/contracts#call-a-contract-function",
-        near_social_account_id
-    );
-
-    if let Ok(request_payload) = near_jsonrpc_client::methods::to_json(&query_view_method_request) {
-        tracing::info!(
-            target: "near_teach_me",
-            parent: &tracing::Span::none(),
-            "HTTP POST {}",
-            network_config.json_rpc_client().server_addr()
-        );
-        tracing::info!(
-            target: "near_teach_me",
-            parent: &tracing::Span::none(),
-            "JSON Request Body:\n{}",
-            near_cli_rs::common::indent_payload(&format!("{:#}", request_payload))
-        );
-    }
+    block_on(crate::api::fetch_remote_components(
+        &crate::rpc::RpcEndpoints::for_network(network_config),
+        near_social_account_id,
+        account_id,
+        social_db_folder,
+        &component_name_list,
+        fetch_options,
+        cache_policy,
+    ))
+}
 
-    let query_view_method_response = network_config
-        .json_rpc_client()
-        .call(&query_view_method_request)
-        .await
-        .inspect_err(|err| match err {
-            near_jsonrpc_client::errors::JsonRpcError::TransportError(transport_error) => {
-                tracing::info!(
-                    target: "near_teach_me",
-                    parent: &tracing::Span::none(),
-                    "JSON RPC Request failed due to connectivity issue:\n{}",
-                    near_cli_rs::common::indent_payload(&format!("{:#?}", transport_error))
-                );
-            }
-            near_jsonrpc_client::errors::JsonRpcError::ServerError(
-                near_jsonrpc_client::errors::JsonRpcServerError::HandlerError(handler_error),
-            ) => {
-                tracing::info!(
-                    target: "near_teach_me",
-                    parent: &tracing::Span::none(),
-                    "JSON RPC Request returned a handling error:\n{}",
-                    near_cli_rs::common::indent_payload(&serde_json::to_string_pretty(handler_error).unwrap_or_else(|_| handler_error.to_string()))
-                );
+pub fn get_updated_components(
+    local_components: crate::api::Components,
+    remote_components: &crate::api::Components,
+) -> crate::api::Components {
+    for (component_name, new_component) in &local_components {
+        if let Some(old_component) = remote_components.get(component_name) {
+            crate::human_print!("Code for component <{component_name}> ");
+            if diff_code(old_component.code(), new_component.code()).is_ok() {
+                crate::human_println!("has not changed");
             }
-            near_jsonrpc_client::errors::JsonRpcError::ServerError(server_error) => {
-                tracing::info!(
-                    target: "near_teach_me",
-                    parent: &tracing::Span::none(),
-                    "JSON RPC Request returned a generic server error:\n{}",
-                    near_cli_rs::common::indent_payload(&format!("{:#?}", server_error))
+            if old_component.metadata() != new_component.metadata()
+                && new_component.metadata().is_some()
+            {
+                crate::human_println!(
+                    "Metadata for component <{component_name}> changed:\n - old metadata: {:?}\n - new metadata: {:?}",
+                    old_component.metadata(), new_component.metadata()
                 );
+            } else {
+                crate::human_println!("Metadata for component <{component_name}> has not changed");
             }
-        })
-        .wrap_err("Failed to query batch of components from Social DB")?;
-
-    query_view_method_response
-        .call_result()
-        .inspect(|call_result| {
-            tracing::info!(
-                target: "near_teach_me",
-                parent: &tracing::Span::none(),
-                "JSON RPC Response:\n{}",
-                near_cli_rs::common::indent_payload(&format!(
-                    "{{\n  \"block_hash\": {}\n  \"block_height\": {}\n  \"logs\": {:?}\n  \"result\": {:?}\n}}",
-                    query_view_method_response.block_hash,
-                    query_view_method_response.block_height,
-                    call_result.logs,
-                    call_result.result
-                ))
-            );
-            tracing::info!(
-                target: "near_teach_me",
-                parent: &tracing::Span::none(),
-                "Decoding the \"result\" array of bytes as UTF-8 string (tip: you can use this Python snippet to do it: `\"\".join([chr(c) for c in result])`):\n{}\n",
-                near_cli_rs::common::indent_payload(
-                    &String::from_utf8(call_result.result.clone())
-                        .unwrap_or_else(|_| "<decoding failed - the result is not a UTF-8 string>".to_owned())
-                )
-            );
-        })
-        .inspect_err(|_| {
-            tracing::info!(
-                target: "near_teach_me",
-                parent: &tracing::Span::none(),
-                "JSON RPC Response:\n{}",
-                near_cli_rs::common::indent_payload("Internal error: Received unexpected query kind in response to a view-function query call")
-            );
-        })?;
-
-    match query_view_method_response.kind {
-        near_jsonrpc_primitives::types::query::QueryResponseKind::CallResult(call_result) => {
-            Ok(call_result
-                .parse_result_from_json::<crate::socialdb_types::SocialDb>()
-                .wrap_err("ERROR: failed to parse Social DB response")?
-                .accounts
-                .remove(account_id)
-                .map(|crate::socialdb_types::SocialDbComponentKey { key }| key)
-                .unwrap_or_default()
-                .remove(social_db_folder)
-                .map(|crate::socialdb_types::SocialDbAccountMetadata { components }| components)
-                .unwrap_or_default())
+        } else {
+            crate::human_println!("Found new component <{component_name}> to deploy");
         }
-        _ => unreachable!("ERROR: unexpected response type from JSON RPC client"),
     }
+    crate::api::plan_deploy(local_components, remote_components).into_components_to_deploy()
 }
 
-pub fn get_updated_components(
-    local_components: HashMap<String, crate::socialdb_types::SocialDbComponent>,
-    remote_components: &HashMap<
-        crate::socialdb_types::ComponentName,
-        crate::socialdb_types::SocialDbComponent,
-    >,
-) -> HashMap<String, crate::socialdb_types::SocialDbComponent> {
-    local_components
-        .into_iter()
-        .filter(|(component_name, new_component)| {
-            if let Some(old_component) = remote_components.get(component_name) {
-                print!("Code for component <{component_name}> ");
-                let has_code_changed = diff_code(old_component.code(), new_component.code()).is_err();
-                let has_metadata_changed = old_component.metadata() != new_component.metadata() && new_component.metadata().is_some();
-                if !has_code_changed {
-                    println!("has not changed");
-                }
-                if has_metadata_changed {
-                    println!(
-                        "Metadata for component <{component_name}> changed:\n - old metadata: {:?}\n - new metadata: {:?}",
-                        old_component.metadata(), new_component.metadata()
-                    );
-                } else {
-                    println!("Metadata for component <{component_name}> has not changed");
+/// Reports the components deployed by a `set` transaction, failing if the transaction failed.
+pub fn print_deployed_components(
+    transaction_info: &near_primitives::views::FinalExecutionOutcomeView,
+    deploy_to_account_id: &near_primitives::types::AccountId,
+    social_db_folder: &str,
+) -> color_eyre::eyre::Result<()> {
+    let args = if let near_primitives::views::FinalExecutionStatus::SuccessValue(_) =
+        transaction_info.status
+    {
+        match &transaction_info.transaction.actions[0] {
+            near_primitives::views::ActionView::FunctionCall { args, .. } => args.to_vec(),
+            // The `set` call relayed as a meta transaction
+            near_primitives::views::ActionView::Delegate {
+                delegate_action, ..
+            } => match delegate_action.get_actions().first() {
+                Some(near_primitives::transaction::Action::FunctionCall(function_call)) => {
+                    function_call.args.clone()
                 }
-                has_code_changed || has_metadata_changed
-            } else {
-                println!("Found new component <{component_name}> to deploy");
-                true
-            }
-        })
-        .collect()
+                _ => color_eyre::eyre::bail!("Internal error: Unexpected delegate action"),
+            },
+            _ => color_eyre::eyre::bail!("Internal error: Unexpected function call arguments",),
+        }
+    } else {
+        color_eyre::eyre::bail!("Components deployment failed!");
+    };
+
+    let transaction_function_args: crate::api::SetFunctionArgs = serde_json::from_slice(&args)
+        .wrap_err("Internal error: Could not parse SocialDB request that we just created.")?;
+
+    let social_account_metadata = transaction_function_args.data.accounts.get(deploy_to_account_id)
+        .wrap_err("Internal error: Could not get the key for the component from SocialDB request that we just created.")?
+        .key
+        .get(social_db_folder)
+        .wrap_err("Internal error: Could not get metadata from SocialDB request that we just created.")?;
+    let updated_components = &social_account_metadata.components;
+
+    crate::human_println!(
+        "\n<{}> components were successfully deployed to <{deploy_to_account_id}>/{social_db_folder}/:",
+        updated_components.len()
+    );
+    for component in updated_components.keys() {
+        crate::human_println!(" * {component}")
+    }
+    crate::human_println!();
+    crate::output::emit(&serde_json::json!({
+        "transaction_hash": transaction_info.transaction_outcome.id,
+        "account_id": deploy_to_account_id,
+        "social_db_folder": social_db_folder,
+        "deployed_components": updated_components.keys().collect::<Vec<_>>(),
+    }))
+}
+
+/// Reports the result of a `set` transaction of `social-db data set`.
+pub fn print_data_set_status(
+    transaction_info: &near_primitives::views::FinalExecutionOutcomeView,
+    set_to_account_id: &near_primitives::types::AccountId,
+) -> color_eyre::eyre::Result<()> {
+    if let near_primitives::views::FinalExecutionStatus::SuccessValue(_) = transaction_info.status {
+        crate::human_println!("Keys successfully installed on <{set_to_account_id}>");
+    } else {
+        color_eyre::eyre::bail!("Keys were not successfully installed on <{set_to_account_id}>");
+    };
+    crate::output::emit(&serde_json::json!({
+        "transaction_hash": transaction_info.transaction_outcome.id,
+        "account_id": set_to_account_id,
+    }))
 }
 
 #[derive(Debug, Clone, serde::Deserialize)]
@@ -352,6 +322,46 @@ pub fn mark_leaf_values_as_null(data: &mut serde_json::Value) {
     }
 }
 
+/// Drops the `null` leaves (the values to delete) from SocialDB data, e.g. to estimate the storage
+/// deposit for the values that are going to be stored
+pub fn without_null_leaves(data: &serde_json::Value) -> Option<serde_json::Value> {
+    match data {
+        serde_json::Value::Null => None,
+        serde_json::Value::Object(object_data) => {
+            let object_data = object_data
+                .iter()
+                .filter_map(|(key, value)| Some((key.clone(), without_null_leaves(value)?)))
+                .collect::<serde_json::Map<_, _>>();
+            (!object_data.is_empty()).then_some(serde_json::Value::Object(object_data))
+        }
+        data => Some(data.clone()),
+    }
+}
+
+/// Merges `new_data` into `data` the way SocialDB `set` merges it into the stored tree
+///
+/// A value set to a node with children becomes the node value (the `""` key).
+pub fn merge_social_db_data(data: &mut serde_json::Value, new_data: &serde_json::Value) {
+    match (data, new_data) {
+        (serde_json::Value::Object(object_data), serde_json::Value::Object(new_object_data)) => {
+            for (key, new_value) in new_object_data {
+                merge_social_db_data(
+                    object_data
+                        .entry(key.clone())
+                        .or_insert(serde_json::Value::Null),
+                    new_value,
+                );
+            }
+        }
+        (serde_json::Value::Object(object_data), new_value) => {
+            object_data.insert(String::new(), new_value.clone());
+        }
+        (data, new_value) => {
+            *data = new_value.clone();
+        }
+    }
+}
+
 pub fn social_db_data_from_key(full_key: &str, data_to_set: &mut serde_json::Value) {
     if let Some((prefix, key)) = full_key.rsplit_once('/') {
         *data_to_set = serde_json::json!({ key: data_to_set });
@@ -360,3 +370,229 @@ pub fn social_db_data_from_key(full_key: &str, data_to_set: &mut serde_json::Val
         *data_to_set = serde_json::json!({ full_key: data_to_set });
     }
 }
+
+/// Flattens SocialDB data into a list of `account/key/path` leaves with their values
+///
+/// The empty key (`""`) holds the value of an intermediate node, so it is reported under the node path.
+pub fn social_db_data_leaves(data: &serde_json::Value) -> Vec<(String, &serde_json::Value)> {
+    fn collect<'a>(
+        path: &str,
+        data: &'a serde_json::Value,
+        leaves: &mut Vec<(String, &'a serde_json::Value)>,
+    ) {
+        match data {
+            serde_json::Value::Object(object_data) => {
+                for (key, value) in object_data {
+                    let path = match (path.is_empty(), key.is_empty()) {
+                        (true, _) => key.clone(),
+                        (false, true) => path.to_owned(),
+                        (false, false) => format!("{path}/{key}"),
+                    };
+                    collect(&path, value, leaves);
+                }
+            }
+            data => leaves.push((path.to_owned(), data)),
+        }
+    }
+
+    let mut leaves = Vec::new();
+    collect("", data, &mut leaves);
+    leaves
+}
+
+fn social_db_value_at<'a>(
+    data: &'a serde_json::Value,
+    path: &str,
+) -> Option<&'a serde_json::Value> {
+    let value = path
+        .split('/')
+        .try_fold(data, |data, key| data.as_object()?.get(key))?;
+    match value {
+        serde_json::Value::Object(object_data) => object_data.get(""),
+        value => Some(value),
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct SocialDbValueChange {
+    pub old: serde_json::Value,
+    pub new: serde_json::Value,
+}
+
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct SocialDbDataDiff {
+    pub added: std::collections::BTreeMap<String, serde_json::Value>,
+    pub updated: std::collections::BTreeMap<String, SocialDbValueChange>,
+    pub removed: std::collections::BTreeMap<String, serde_json::Value>,
+    pub unchanged: Vec<String>,
+}
+
+impl SocialDbDataDiff {
+    pub fn has_changes(&self) -> bool {
+        !(self.added.is_empty() && self.updated.is_empty() && self.removed.is_empty())
+    }
+}
+
+/// Compares the data that is going to be sent with `set` against the current SocialDB state
+///
+/// SocialDB merges the new data into the existing tree, so only the leaves present in the new data
+/// are reported; `null` leaves are the ones that are going to be removed.
+pub fn diff_social_db_data(
+    remote_data: Option<&serde_json::Value>,
+    new_data: &serde_json::Value,
+) -> SocialDbDataDiff {
+    let mut diff = SocialDbDataDiff::default();
+    for (path, new_value) in social_db_data_leaves(new_data) {
+        let old_value = remote_data
+            .and_then(|remote_data| social_db_value_at(remote_data, &path))
+            .filter(|old_value| !old_value.is_null());
+        match (old_value, new_value) {
+            (None, serde_json::Value::Null) => {}
+            (None, new_value) => {
+                diff.added.insert(path, new_value.clone());
+            }
+            (Some(old_value), serde_json::Value::Null) => {
+                diff.removed.insert(path, old_value.clone());
+            }
+            (Some(old_value), new_value) if old_value == new_value => {
+                diff.unchanged.push(path);
+            }
+            (Some(old_value), new_value) => {
+                diff.updated.insert(
+                    path,
+                    SocialDbValueChange {
+                        old: old_value.clone(),
+                        new: new_value.clone(),
+                    },
+                );
+            }
+        }
+    }
+    diff
+}
+
+pub fn get_storage_balance(
+    network_config: &near_cli_rs::config::NetworkConfig,
+    near_social_account_id: &near_primitives::types::AccountId,
+    account_id: &near_primitives::types::AccountId,
+) -> color_eyre::eyre::Result<Option<StorageBalance>> {
+    network_config
+        .json_rpc_client()
+        .blocking_call_view_function(
+            near_social_account_id,
+            "storage_balance_of",
+            serde_json::json!({
+                "account_id": account_id,
+            })
+            .to_string()
+            .into_bytes(),
+            near_primitives::types::Finality::Final.into(),
+        )
+        .wrap_err_with(|| {
+            format!("Failed to fetch query for view method: 'storage_balance_of {account_id}'")
+        })?
+        .parse_result_from_json()
+        .wrap_err("SocialDB `storage_balance_of` response cannot be parsed")
+}
+
+/// Renders SocialDB data as a tree of keys, optionally followed by the leaf values
+pub fn social_db_data_tree(data: &serde_json::Value, with_values: bool) -> String {
+    fn render(data: &serde_json::Value, with_values: bool, prefix: &str, tree: &mut String) {
+        let Some(object_data) = data.as_object() else {
+            return;
+        };
+        for (idx, (key, value)) in object_data.iter().enumerate() {
+            let is_last = idx + 1 == object_data.len();
+            let key = if key.is_empty() { "\"\"" } else { key };
+            tree.push_str(prefix);
+            tree.push_str(if is_last { "└── " } else { "├── " });
+            tree.push_str(key);
+            if with_values && !value.is_object() {
+                tree.push_str(" = ");
+                tree.push_str(
+                    &value
+                        .as_str()
+                        .map_or_else(|| value.to_string(), str::to_owned),
+                );
+            }
+            tree.push('\n');
+            render(
+                value,
+                with_values,
+                &format!("{prefix}{}", if is_last { "    " } else { "│   " }),
+                tree,
+            );
+        }
+    }
+
+    let mut tree = String::new();
+    if let Some(object_data) = data.as_object() {
+        for (key, value) in object_data {
+            tree.push_str(key);
+            tree.push('\n');
+            render(value, with_values, "", &mut tree);
+        }
+    }
+    tree
+}
+
+/// Estimates the number of storage bytes that will be released once the given SocialDB leaves are nulled
+///
+/// Mirrors the size estimate used for the storage deposit, so the per-key and per-node overhead is
+/// included. The account nodes themselves are kept by SocialDB, so only the data under them counts.
+pub fn estimate_released_storage_bytes(data: &serde_json::Value) -> usize {
+    fn entry_size(key: &str, value: &serde_json::Value) -> usize {
+        let value_size = match value {
+            serde_json::Value::Null => return 0,
+            serde_json::Value::Object(data) => {
+                crate::consts::ESTIMATED_NODE_SIZE
+                    + data
+                        .iter()
+                        .map(|(key, value)| entry_size(key, value))
+                        .sum::<usize>()
+            }
+            serde_json::Value::String(value) => value.len().max(8),
+            value => value.to_string().len().max(8),
+        };
+        key.len() * 2 + value_size + crate::consts::ESTIMATED_KEY_VALUE_SIZE
+    }
+
+    data.as_object()
+        .into_iter()
+        .flat_map(|accounts| accounts.values())
+        .filter_map(serde_json::Value::as_object)
+        .flatten()
+        .map(|(key, value)| entry_size(key, value))
+        .sum()
+}
+
+/// Prefixes a relative key (e.g. `widget/Nav`) with the signer account ID and makes sure
+/// that an absolute key (e.g. `frol.near/widget`) belongs to the signer.
+///
+/// A key is absolute when its first segment is the signer, a sub-account or an implicit account
+/// ID. Any other key is relative, including the ones starting with a name that could also be a
+/// top-level account ID (e.g. `widget/Nav` becomes `<signer>/widget/Nav`).
+pub fn social_db_key_for_signer(
+    signer_account_id: &near_primitives::types::AccountId,
+    social_db_key: &str,
+) -> color_eyre::eyre::Result<String> {
+    let social_db_key = social_db_key.trim_matches('/');
+    if social_db_key.is_empty() {
+        color_eyre::eyre::bail!("The social_db key prefix must not be empty");
+    }
+    let first_segment = social_db_key
+        .split_once('/')
+        .map_or(social_db_key, |(first_segment, _)| first_segment);
+    let Ok(account_id) = first_segment.parse::<near_primitives::types::AccountId>() else {
+        return Ok(format!("{signer_account_id}/{social_db_key}"));
+    };
+    if &account_id == signer_account_id {
+        return Ok(social_db_key.to_owned());
+    }
+    if !account_id.is_top_level() || account_id.get_account_type().is_implicit() {
+        color_eyre::eyre::bail!(
+            "The social_db key <{social_db_key}> does not start with the signer account <{signer_account_id}>"
+        );
+    }
+    Ok(format!("{signer_account_id}/{social_db_key}"))
+}
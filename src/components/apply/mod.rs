@@ -0,0 +1,46 @@
+use color_eyre::eyre::WrapErr;
+
+mod sign_as;
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = super::ComponentsContext)]
+#[interactive_clap(output_context = ApplyCmdContext)]
+pub struct ApplyCmd {
+    /// What is the path to the deploy plan (made with `bos components plan`)?
+    plan_file: near_cli_rs::types::path_buf::PathBuf,
+    #[interactive_clap(named_arg)]
+    /// Specify signer account ID
+    sign_as: self::sign_as::Signer,
+}
+
+#[derive(Clone)]
+pub struct ApplyCmdContext {
+    pub global_context: near_cli_rs::GlobalContext,
+    pub fetch_options: crate::api::FetchOptions,
+    pub plan: crate::api::DeployPlanFile,
+}
+
+impl ApplyCmdContext {
+    pub fn from_previous_context(
+        previous_context: super::ComponentsContext,
+        scope: &<ApplyCmd as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let plan_file = std::fs::read(&scope.plan_file.0).wrap_err_with(|| {
+            format!(
+                "Failed to read the plan from {}",
+                scope.plan_file.0.display()
+            )
+        })?;
+        let plan = serde_json::from_slice(&plan_file).wrap_err_with(|| {
+            format!(
+                "The file {} is not a deploy plan made with `bos components plan`",
+                scope.plan_file.0.display()
+            )
+        })?;
+        Ok(Self {
+            global_context: previous_context.global_context,
+            fetch_options: previous_context.fetch_options,
+            plan,
+        })
+    }
+}
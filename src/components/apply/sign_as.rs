@@ -0,0 +1,142 @@
+use std::sync::Arc;
+
+use inquire::CustomType;
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = super::ApplyCmdContext)]
+#[interactive_clap(output_context = SignerContext)]
+pub struct Signer {
+    #[interactive_clap(skip_default_input_arg)]
+    /// What is the signer account ID?
+    signer_account_id: near_cli_rs::types::account_id::AccountId,
+    #[interactive_clap(named_arg)]
+    /// Select network
+    network_config: near_cli_rs::network_for_transaction::NetworkForTransactionArgs,
+}
+
+#[derive(Clone)]
+pub struct SignerContext(near_cli_rs::commands::ActionContext);
+
+impl SignerContext {
+    pub fn from_previous_context(
+        previous_context: super::ApplyCmdContext,
+        scope: &<Signer as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let plan = Arc::new(previous_context.plan);
+        let signer_id: near_primitives::types::AccountId = scope.signer_account_id.clone().into();
+        let fetch_options = previous_context.fetch_options;
+
+        let get_prepopulated_transaction_after_getting_network_callback: near_cli_rs::commands::GetPrepopulatedTransactionAfterGettingNetworkCallback = Arc::new({
+            let plan = plan.clone();
+            let signer_id = signer_id.clone();
+            move |network_config| {
+                if network_config.network_name != plan.network {
+                    color_eyre::eyre::bail!(
+                        "The plan was made for network <{}>, not <{}>",
+                        plan.network,
+                        network_config.network_name
+                    );
+                }
+                let near_social_account_id = crate::common::get_near_social_account_id(network_config)?;
+                if near_social_account_id != plan.near_social_account_id {
+                    color_eyre::eyre::bail!(
+                        "The plan was made for the SocialDB contract <{}>, not <{near_social_account_id}>",
+                        plan.near_social_account_id
+                    );
+                }
+
+                // The verification has to see the current state, not a cached response
+                let remote_components = crate::common::get_remote_components(
+                    network_config,
+                    plan.components.keys().collect(),
+                    &near_social_account_id,
+                    &plan.account_id,
+                    &plan.social_db_folder,
+                    fetch_options,
+                    crate::cache::CachePolicy::Bypass,
+                )?;
+                plan.verify_remote_components(&remote_components)?;
+                crate::human_println!(
+                    "\nThe deployed components have not changed since the plan was made at block #{}.",
+                    plan.block_height
+                );
+
+                Ok(near_cli_rs::commands::PrepopulatedTransaction {
+                    signer_id: signer_id.clone(),
+                    receiver_id: near_social_account_id,
+                    actions: vec![near_primitives::transaction::Action::FunctionCall(Box::new(
+                        plan.set_function_call()?,
+                    ))],
+                })
+            }
+        });
+
+        let on_before_signing_callback: near_cli_rs::commands::OnBeforeSigningCallback =
+            Arc::new({
+                let plan = plan.clone();
+                move |prepopulated_unsigned_transaction, network_config| {
+                    let json_rpc_client = network_config.json_rpc_client();
+                    let public_key = prepopulated_unsigned_transaction.public_key.clone();
+                    let receiver_id = prepopulated_unsigned_transaction.receiver_id.clone();
+                    if let Some(near_primitives::transaction::Action::FunctionCall(action)) =
+                        prepopulated_unsigned_transaction.actions.get_mut(0)
+                    {
+                        action.deposit =
+                            crate::common::block_on(near_socialdb_client::get_deposit(
+                                &json_rpc_client,
+                                &signer_id,
+                                &public_key,
+                                &plan.account_id,
+                                &plan.social_db_folder,
+                                &receiver_id,
+                                near_cli_rs::types::near_token::NearToken::from_yoctonear(
+                                    action.deposit,
+                                )
+                                .into(),
+                            ))?
+                            .as_yoctonear();
+                        Ok(())
+                    } else {
+                        color_eyre::eyre::bail!("Unexpected action to change components",);
+                    }
+                }
+            });
+
+        let on_after_sending_transaction_callback: near_cli_rs::transaction_signature_options::OnAfterSendingTransactionCallback = Arc::new({
+            let plan = plan.clone();
+            move |transaction_info, _network_config| {
+                crate::cache::invalidate_final_block_entries();
+                crate::common::print_deployed_components(transaction_info, &plan.account_id, &plan.social_db_folder)
+            }
+        });
+
+        Ok(Self(near_cli_rs::commands::ActionContext {
+            global_context: previous_context.global_context,
+            interacting_with_account_ids: vec![scope.signer_account_id.clone().into()],
+            get_prepopulated_transaction_after_getting_network_callback,
+            on_before_signing_callback,
+            on_before_sending_transaction_callback: Arc::new(
+                |_signed_transaction, _network_config| Ok(String::new()),
+            ),
+            on_after_sending_transaction_callback,
+        }))
+    }
+}
+
+impl From<SignerContext> for near_cli_rs::commands::ActionContext {
+    fn from(item: SignerContext) -> Self {
+        item.0
+    }
+}
+
+impl Signer {
+    fn input_signer_account_id(
+        context: &super::ApplyCmdContext,
+    ) -> color_eyre::eyre::Result<Option<near_cli_rs::types::account_id::AccountId>> {
+        Ok(Some(
+            CustomType::new("What is the signer account ID?")
+                .with_default(context.plan.account_id.clone().into())
+                .prompt()?,
+        ))
+    }
+}
@@ -1,6 +1,6 @@
-use color_eyre::eyre::{ContextCompat, WrapErr};
+use color_eyre::eyre::WrapErr;
 use inquire::{CustomType, Select};
-use near_cli_rs::common::{CallResultExt, JsonRpcClientExt};
+use near_cli_rs::common::CallResultExt;
 
 #[derive(Debug, Clone, interactive_clap::InteractiveClap)]
 #[interactive_clap(input_context = super::selected::ComponentContext)]
@@ -47,8 +47,7 @@ impl From<SignerContext> for near_cli_rs::commands::ActionContext {
             let social_db_folder = item.social_db_folder.clone();
 
             move |network_config| {
-                let near_social_account_id = crate::consts::NEAR_SOCIAL_ACCOUNT_ID.get(network_config.network_name.as_str())
-                    .wrap_err_with(|| format!("The <{}> network does not have a near-social contract.", network_config.network_name))?;
+                let near_social_account_id = &crate::common::get_near_social_account_id(network_config)?;
 
                 let keys_components_to_remove = if components.is_empty() {
                     vec![format!("{account_id}/{social_db_folder}/**")]
@@ -61,22 +60,23 @@ impl From<SignerContext> for near_cli_rs::commands::ActionContext {
 
                 let input_args = serde_json::to_string(&crate::socialdb_types::SocialDbQuery {
                     keys: keys_components_to_remove,
+                    options: None,
                 })
                 .wrap_err("Internal error: could not serialize SocialDB input args")?;
 
-                let mut social_db_data_to_remove: serde_json::Value = network_config
-                    .json_rpc_client()
-                    .blocking_call_view_function(
+                let mut social_db_data_to_remove: serde_json::Value = crate::common::call_view_function_with_failover(
+                        network_config,
                         near_social_account_id,
                         "get",
                         input_args.into_bytes(),
                         near_primitives::types::Finality::Final.into(),
+                        crate::cache::CachePolicy::Bypass,
                     )
                     .wrap_err("Failed to fetch the components from SocialDB")?
                     .parse_result_from_json()
                     .wrap_err("SocialDB `get` data response cannot be parsed")?;
                 if social_db_data_to_remove.as_object().map(|result| result.is_empty()).unwrap_or(true) {
-                    println!("No components to remove. Goodbye.");
+                    crate::human_println!("No components to remove. Goodbye.");
                     return Ok(near_cli_rs::commands::PrepopulatedTransaction {
                         signer_id: signer_id.clone(),
                         receiver_id: near_social_account_id.clone(),
@@ -106,12 +106,17 @@ impl From<SignerContext> for near_cli_rs::commands::ActionContext {
             let account_id = item.account_id.clone();
 
             move |transaction_info, _network_config| {
+                crate::cache::invalidate_final_block_entries();
                 if let near_primitives::views::FinalExecutionStatus::SuccessValue(_) = transaction_info.status {
-                    println!("The components were deleted successfully from <{}>/{}/", &account_id, item.social_db_folder);
+                    crate::human_println!("The components were deleted successfully from <{}>/{}/", &account_id, item.social_db_folder);
                 } else {
                     color_eyre::eyre::bail!("The components were not successfully deleted from <{}>/{}/", &account_id, item.social_db_folder);
                 };
-                Ok(())
+                crate::output::emit(&serde_json::json!({
+                    "transaction_hash": transaction_info.transaction_outcome.id,
+                    "account_id": account_id.to_string(),
+                    "social_db_folder": item.social_db_folder,
+                }))
             }
         });
 
@@ -143,7 +148,7 @@ impl Signer {
                 &context.global_context.config.network_connection,
                 signer_account_id.clone().into(),
             ) {
-                println!("\nThe account <{signer_account_id}> does not yet exist.");
+                crate::human_println!("\nThe account <{signer_account_id}> does not yet exist.");
                 #[derive(strum_macros::Display)]
                 enum ConfirmOptions {
                     #[strum(to_string = "Yes, I want to enter a new account name.")]
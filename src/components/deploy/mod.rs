@@ -1,13 +1,11 @@
 use color_eyre::eyre::ContextCompat;
 use inquire::Select;
+use strum::{EnumDiscriminants, EnumIter, EnumMessage};
 
+mod propose_as;
+mod relay_as;
 mod sign_as;
 
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
-pub struct TransactionFunctionArgs {
-    pub data: crate::socialdb_types::SocialDb,
-}
-
 #[derive(Debug, Clone, interactive_clap::InteractiveClap)]
 #[interactive_clap(input_context = super::ComponentsContext)]
 #[interactive_clap(output_context = DeployCmdContext)]
@@ -15,15 +13,37 @@ pub struct DeployCmd {
     #[interactive_clap(skip_default_input_arg)]
     /// Which account do you want to deploy the components to?
     deploy_to_account_id: near_cli_rs::types::account_id::AccountId,
-    #[interactive_clap(named_arg)]
+    #[interactive_clap(subcommand)]
+    deploy_mode: DeployMode,
+}
+
+#[derive(Debug, EnumDiscriminants, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(context = DeployCmdContext)]
+#[strum_discriminants(derive(EnumMessage, EnumIter))]
+/// How do you want to deploy the components?
+pub enum DeployMode {
+    #[strum_discriminants(strum(
+        message = "sign-as      -   Sign the deploy transaction with an account"
+    ))]
     /// Specify signer account ID
-    sign_as: self::sign_as::Signer,
+    SignAs(self::sign_as::Signer),
+    #[strum_discriminants(strum(
+        message = "propose-as   -   Submit the deploy as a proposal to the Sputnik DAO that owns the account"
+    ))]
+    /// Submit the deploy as a proposal to the Sputnik DAO that owns the account
+    ProposeAs(self::propose_as::Proposer),
+    #[strum_discriminants(strum(
+        message = "relay-as     -   Sign the deploy as a delegate action and send it to a relayer"
+    ))]
+    /// Sign the deploy as a delegate action and send it to a relayer that pays for the gas
+    RelayAs(self::relay_as::Relayer),
 }
 
 #[derive(Clone)]
 pub struct DeployCmdContext {
     pub global_context: near_cli_rs::GlobalContext,
     pub social_db_folder: String,
+    pub fetch_options: crate::api::FetchOptions,
     pub deploy_to_account_id: near_cli_rs::types::account_id::AccountId,
 }
 
@@ -35,6 +55,7 @@ impl DeployCmdContext {
         Ok(Self {
             global_context: previous_context.global_context,
             social_db_folder: previous_context.social_db_folder,
+            fetch_options: previous_context.fetch_options,
             deploy_to_account_id: scope.deploy_to_account_id.clone(),
         })
     }
@@ -45,12 +66,12 @@ impl DeployCmd {
         context: &super::ComponentsContext,
     ) -> color_eyre::eyre::Result<Option<near_cli_rs::types::account_id::AccountId>> {
         let components = crate::common::get_local_components()?;
-        println!(
+        crate::human_println!(
             "\nThere are <{}> components in the current folder ready for deployment:",
             components.len()
         );
         for component in components.keys() {
-            println!(" * {component}")
+            crate::human_println!(" * {component}")
         }
         loop {
             let deploy_to_account_id =
@@ -63,7 +84,7 @@ impl DeployCmd {
                 &context.global_context.config.network_connection,
                 deploy_to_account_id.clone().into(),
             ) {
-                println!(
+                crate::human_println!(
                     "\nThe account <{}> does not yet exist.",
                     &deploy_to_account_id
                 );
@@ -88,3 +109,85 @@ impl DeployCmd {
         }
     }
 }
+
+/// The SocialDB `set` call deploying the new and updated local components.
+pub struct PreparedDeploy {
+    pub set_function_call: near_primitives::transaction::FunctionCallAction,
+    /// One line per deployed component: whether it is new or how many lines have changed.
+    pub changes_summary: Vec<String>,
+}
+
+/// Compares the local components with the deployed ones and builds the `set` call, or returns
+/// `None` if there is nothing to deploy.
+pub fn prepare_deploy(
+    network_config: &near_cli_rs::config::NetworkConfig,
+    near_social_account_id: &near_primitives::types::AccountId,
+    deploy_to_account_id: &near_primitives::types::AccountId,
+    social_db_folder: &str,
+    fetch_options: crate::api::FetchOptions,
+) -> color_eyre::eyre::Result<Option<PreparedDeploy>> {
+    let local_components = crate::common::get_local_components()?;
+    if local_components.is_empty() {
+        crate::human_println!("There are no components in the current ./src folder. Goodbye.");
+        return Ok(None);
+    }
+    let local_component_name_list = local_components.keys().collect::<Vec<_>>();
+    let remote_components = crate::common::get_remote_components(
+        network_config,
+        local_component_name_list,
+        near_social_account_id,
+        deploy_to_account_id,
+        social_db_folder,
+        fetch_options,
+        crate::cache::CachePolicy::Bypass,
+    )?;
+
+    let components_to_deploy = if !remote_components.is_empty() {
+        let updated_components =
+            crate::common::get_updated_components(local_components, &remote_components);
+        if updated_components.is_empty() {
+            crate::human_println!(
+                "There are no new or modified components in the current ./src folder. Goodbye."
+            );
+            return Ok(None);
+        }
+        updated_components
+    } else {
+        crate::human_println!(
+            "\nAll local components will be deployed to <{deploy_to_account_id}> as new."
+        );
+        local_components
+    };
+
+    let mut component_names = components_to_deploy.keys().collect::<Vec<_>>();
+    component_names.sort();
+    let changes_summary = component_names
+        .into_iter()
+        .map(
+            |component_name| match remote_components.get(component_name) {
+                Some(remote_component) => {
+                    let (inserted, deleted) = crate::common::count_changed_lines(
+                        remote_component.code(),
+                        components_to_deploy[component_name].code(),
+                    );
+                    format!("{component_name} (updated: +{inserted} -{deleted} lines)")
+                }
+                None => format!("{component_name} (new)"),
+            },
+        )
+        .collect();
+
+    let set_function_call = crate::common::block_on(crate::api::build_set_function_call(
+        &network_config.json_rpc_client(),
+        near_social_account_id,
+        deploy_to_account_id,
+        social_db_folder,
+        components_to_deploy,
+        remote_components,
+    ))?;
+
+    Ok(Some(PreparedDeploy {
+        set_function_call,
+        changes_summary,
+    }))
+}
@@ -0,0 +1,197 @@
+use std::sync::Arc;
+
+use color_eyre::eyre::WrapErr;
+use inquire::CustomType;
+use near_cli_rs::common::CallResultExt;
+
+/// The gas for the `set` call executed by the DAO once the proposal is approved (the DAO needs
+/// some gas on top of it, so it is less than the gas of a directly signed deploy).
+const PROPOSAL_SET_GAS_TGAS: u64 = 200;
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = super::DeployCmdContext)]
+#[interactive_clap(output_context = ProposerContext)]
+pub struct Proposer {
+    #[interactive_clap(skip_default_input_arg)]
+    /// Which DAO member account submits the proposal?
+    proposer_account_id: near_cli_rs::types::account_id::AccountId,
+    /// Text to put at the top of the generated proposal description
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    description: Option<String>,
+    #[interactive_clap(named_arg)]
+    /// Select network
+    network_config: near_cli_rs::network_for_transaction::NetworkForTransactionArgs,
+}
+
+#[derive(Clone)]
+pub struct ProposerContext(near_cli_rs::commands::ActionContext);
+
+impl ProposerContext {
+    pub fn from_previous_context(
+        previous_context: super::DeployCmdContext,
+        scope: &<Proposer as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let dao_account_id: near_primitives::types::AccountId =
+            previous_context.deploy_to_account_id.clone().into();
+        let proposer_account_id: near_primitives::types::AccountId =
+            scope.proposer_account_id.clone().into();
+        let social_db_folder = previous_context.social_db_folder.clone();
+        let fetch_options = previous_context.fetch_options;
+        let description = scope.description.clone();
+
+        let get_prepopulated_transaction_after_getting_network_callback: near_cli_rs::commands::GetPrepopulatedTransactionAfterGettingNetworkCallback = Arc::new({
+            let dao_account_id = dao_account_id.clone();
+            move |network_config| {
+                let near_social_account_id = &crate::common::get_near_social_account_id(network_config)?;
+                let mut prepopulated_transaction = near_cli_rs::commands::PrepopulatedTransaction {
+                    signer_id: proposer_account_id.clone(),
+                    receiver_id: dao_account_id.clone(),
+                    actions: vec![],
+                };
+                let Some(prepared_deploy) = super::prepare_deploy(
+                    network_config,
+                    near_social_account_id,
+                    &dao_account_id,
+                    &social_db_folder,
+                    fetch_options,
+                )? else {
+                    return Ok(prepopulated_transaction);
+                };
+
+                let mut proposal_description = description.clone().map(|description| format!("{description}\n\n")).unwrap_or_default();
+                proposal_description.push_str(&format!(
+                    "Deploy {} components to {dao_account_id}/{social_db_folder}/ on {near_social_account_id}:",
+                    prepared_deploy.changes_summary.len()
+                ));
+                for change in &prepared_deploy.changes_summary {
+                    proposal_description.push_str(&format!("\n * {change}"));
+                }
+
+                let set_function_call = prepared_deploy.set_function_call;
+                // Like a full access key signer, the DAO attaches at least 1 yoctoNEAR to its own `set`
+                let set_deposit = set_function_call.deposit.max(1);
+                let proposal_bond = get_proposal_bond(network_config, &dao_account_id)?;
+
+                crate::human_println!(
+                    "\nThe following proposal will be submitted to <{dao_account_id}> with a bond of {}:\n{}",
+                    near_cli_rs::types::near_token::NearToken::from_yoctonear(proposal_bond),
+                    near_cli_rs::common::indent_payload(&proposal_description)
+                );
+                crate::human_println!(
+                    "Once the proposal is approved, the DAO will attach {} for the SocialDB storage.",
+                    near_cli_rs::types::near_token::NearToken::from_yoctonear(set_deposit)
+                );
+
+                let add_proposal_args = serde_json::json!({
+                    "proposal": {
+                        "description": proposal_description,
+                        "kind": {
+                            "FunctionCall": {
+                                "receiver_id": near_social_account_id,
+                                "actions": [{
+                                    "method_name": set_function_call.method_name,
+                                    "args": near_primitives::serialize::to_base64(&set_function_call.args),
+                                    "deposit": set_deposit.to_string(),
+                                    "gas": near_cli_rs::common::NearGas::from_tgas(PROPOSAL_SET_GAS_TGAS).as_gas().to_string(),
+                                }],
+                            }
+                        }
+                    }
+                });
+
+                prepopulated_transaction.actions = vec![
+                    near_primitives::transaction::Action::FunctionCall(Box::new(
+                        near_primitives::transaction::FunctionCallAction {
+                            method_name: "add_proposal".to_string(),
+                            args: serde_json::to_vec(&add_proposal_args)?,
+                            gas: near_cli_rs::common::NearGas::from_tgas(300).as_gas(),
+                            deposit: proposal_bond,
+                        },
+                    ))
+                ];
+
+                Ok(prepopulated_transaction)
+            }
+        });
+
+        let on_after_sending_transaction_callback: near_cli_rs::transaction_signature_options::OnAfterSendingTransactionCallback = Arc::new({
+            let dao_account_id = dao_account_id.clone();
+            move |transaction_info, _network_config| {
+                let near_primitives::views::FinalExecutionStatus::SuccessValue(proposal_id) = &transaction_info.status else {
+                    color_eyre::eyre::bail!("Failed to add the deploy proposal to <{dao_account_id}>");
+                };
+                let proposal_id = serde_json::from_slice::<u64>(proposal_id).ok();
+                match proposal_id {
+                    Some(proposal_id) => crate::human_println!("\nProposal #{proposal_id} was added to <{dao_account_id}>."),
+                    None => crate::human_println!("\nThe proposal was added to <{dao_account_id}>."),
+                }
+                crate::human_println!("The components will be deployed once the DAO members approve it.");
+                crate::output::emit(&serde_json::json!({
+                    "transaction_hash": transaction_info.transaction_outcome.id,
+                    "dao_account_id": dao_account_id,
+                    "proposal_id": proposal_id,
+                }))
+            }
+        });
+
+        Ok(Self(near_cli_rs::commands::ActionContext {
+            global_context: previous_context.global_context,
+            interacting_with_account_ids: vec![
+                scope.proposer_account_id.clone().into(),
+                dao_account_id,
+            ],
+            get_prepopulated_transaction_after_getting_network_callback,
+            on_before_signing_callback: Arc::new(
+                |_prepopulated_unsigned_transaction, _network_config| Ok(()),
+            ),
+            on_before_sending_transaction_callback: Arc::new(
+                |_signed_transaction, _network_config| Ok(String::new()),
+            ),
+            on_after_sending_transaction_callback,
+        }))
+    }
+}
+
+impl From<ProposerContext> for near_cli_rs::commands::ActionContext {
+    fn from(item: ProposerContext) -> Self {
+        item.0
+    }
+}
+
+#[derive(serde::Deserialize)]
+struct DaoPolicy {
+    proposal_bond: String,
+}
+
+/// The deposit the Sputnik DAO requires to add a proposal.
+fn get_proposal_bond(
+    network_config: &near_cli_rs::config::NetworkConfig,
+    dao_account_id: &near_primitives::types::AccountId,
+) -> color_eyre::eyre::Result<u128> {
+    let policy: DaoPolicy = crate::common::call_view_function_with_failover(
+        network_config,
+        dao_account_id,
+        "get_policy",
+        b"{}".to_vec(),
+        near_primitives::types::Finality::Final.into(),
+        crate::cache::CachePolicy::Bypass,
+    )
+    .wrap_err_with(|| format!("Failed to fetch the policy of the <{dao_account_id}> DAO"))?
+    .parse_result_from_json()
+    .wrap_err_with(|| format!("<{dao_account_id}> is not a Sputnik DAO contract"))?;
+    policy
+        .proposal_bond
+        .parse()
+        .wrap_err("Invalid proposal bond in the DAO policy")
+}
+
+impl Proposer {
+    fn input_proposer_account_id(
+        _context: &super::DeployCmdContext,
+    ) -> color_eyre::eyre::Result<Option<near_cli_rs::types::account_id::AccountId>> {
+        Ok(Some(
+            CustomType::new("Which DAO member account submits the proposal?").prompt()?,
+        ))
+    }
+}
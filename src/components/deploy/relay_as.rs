@@ -0,0 +1,386 @@
+use std::sync::{Arc, Mutex};
+
+use color_eyre::eyre::{ContextCompat, WrapErr};
+use inquire::CustomType;
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = super::DeployCmdContext)]
+#[interactive_clap(output_context = RelayerContext)]
+#[interactive_clap(skip_default_from_cli)]
+pub struct Relayer {
+    #[interactive_clap(skip_default_input_arg)]
+    /// Which account signs the delegate action?
+    signer_account_id: near_cli_rs::types::account_id::AccountId,
+    /// The relayer endpoint (defaults to the meta_transaction_relayer_url of the network)
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    relayer_url: Option<near_cli_rs::types::url::Url>,
+    #[interactive_clap(named_arg)]
+    /// Select network
+    network_config: near_cli_rs::network_for_transaction::NetworkForTransactionArgs,
+}
+
+#[derive(Clone)]
+pub struct RelayerContext {
+    global_context: near_cli_rs::GlobalContext,
+    social_db_folder: String,
+    fetch_options: crate::api::FetchOptions,
+    deploy_to_account_id: near_primitives::types::AccountId,
+    signer_account_id: near_primitives::types::AccountId,
+    /// The delegate action signed by near CLI.
+    signed_delegate_action:
+        Arc<Mutex<Option<near_primitives::action::delegate::SignedDelegateAction>>>,
+}
+
+impl RelayerContext {
+    /// near CLI signs a delegate action instead of a transaction once the selected network has a
+    /// `meta_transaction_relayer_url`, so `--relayer-url` only overrides it on that network.
+    pub fn from_previous_context(
+        previous_context: super::DeployCmdContext,
+        scope: &<Relayer as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+        network_name: &str,
+    ) -> color_eyre::eyre::Result<Self> {
+        let mut global_context = previous_context.global_context;
+        if let Some(relayer_url) = &scope.relayer_url {
+            global_context
+                .config
+                .network_connection
+                .get_mut(network_name)
+                .wrap_err_with(|| {
+                    format!("There is no network <{network_name}> in the near CLI config")
+                })?
+                .meta_transaction_relayer_url = Some(relayer_url.0.clone());
+        }
+        Ok(Self {
+            global_context,
+            social_db_folder: previous_context.social_db_folder,
+            fetch_options: previous_context.fetch_options,
+            deploy_to_account_id: previous_context.deploy_to_account_id.into(),
+            signer_account_id: scope.signer_account_id.clone().into(),
+            signed_delegate_action: Arc::new(Mutex::new(None)),
+        })
+    }
+
+    /// Sends the signed delegate action to the relayer and reports the transaction from the
+    /// relayer response (near CLI only prints the response, so bos sends it itself).
+    fn relay_delegate_action(&self, network_name: &str) -> color_eyre::eyre::Result<()> {
+        let Some(signed_delegate_action) = self.signed_delegate_action.lock().unwrap().clone()
+        else {
+            return Ok(());
+        };
+        let network_config = self
+            .global_context
+            .config
+            .network_connection
+            .get(network_name)
+            .wrap_err_with(|| {
+                format!("There is no network <{network_name}> in the near CLI config")
+            })?;
+        let relayer_url = network_config
+            .meta_transaction_relayer_url
+            .as_ref()
+            .wrap_err("Internal error: the relayer URL is not set")?;
+
+        let response_text = send_delegate_action(relayer_url, signed_delegate_action)?;
+        crate::cache::invalidate_final_block_entries();
+        match serde_json::from_str::<near_primitives::views::FinalExecutionOutcomeView>(
+            &response_text,
+        ) {
+            Ok(transaction_info) => {
+                near_cli_rs::common::print_transaction_status(
+                    &transaction_info,
+                    network_config,
+                    &self.global_context.verbosity,
+                )?;
+                crate::common::print_deployed_components(
+                    &transaction_info,
+                    &self.deploy_to_account_id,
+                    &self.social_db_folder,
+                )
+            }
+            // Some relayers only respond with a message (e.g. with the transaction hash)
+            Err(_) => {
+                crate::human_println!(
+                    "\nThe relayer accepted the delegate action: {response_text}"
+                );
+                crate::output::emit(&serde_json::json!({
+                    "transaction_hash": null,
+                    "account_id": self.deploy_to_account_id,
+                    "social_db_folder": self.social_db_folder,
+                    "relayer_response": response_text,
+                }))
+            }
+        }
+    }
+}
+
+#[tracing::instrument(name = "Sending the delegate action to the relayer", skip_all)]
+fn send_delegate_action(
+    relayer_url: &url::Url,
+    signed_delegate_action: near_primitives::action::delegate::SignedDelegateAction,
+) -> color_eyre::eyre::Result<String> {
+    tracing::info!(
+        target: "near_teach_me",
+        parent: &tracing::Span::none(),
+        "HTTP POST {relayer_url}"
+    );
+    let response = reqwest::blocking::Client::new()
+        .post(relayer_url.clone())
+        .json(&serde_json::json!({
+            "signed_delegate_action": near_cli_rs::types::signed_delegate_action::SignedDelegateActionAsBase64::from(
+                signed_delegate_action
+            )
+            .to_string()
+        }))
+        .send()
+        .wrap_err_with(|| {
+            format!("Failed to send the delegate action to the relayer <{relayer_url}>")
+        })?;
+    let status = response.status();
+    let text = response
+        .text()
+        .wrap_err("Failed to read the relayer response")?;
+    if !status.is_success() {
+        color_eyre::eyre::bail!(
+            "The relayer <{relayer_url}> rejected the delegate action ({status}): {text}"
+        );
+    }
+    Ok(text)
+}
+
+/// The submit step of the selected signing option (`sign-later` has none).
+fn submit_mut(
+    sign_with: &mut near_cli_rs::transaction_signature_options::CliSignWith,
+) -> Option<&mut Option<near_cli_rs::transaction_signature_options::CliSubmit>> {
+    use near_cli_rs::transaction_signature_options::CliSignWith;
+
+    match sign_with {
+        CliSignWith::SignWithKeychain(sign) => Some(&mut sign.submit),
+        CliSignWith::SignWithLegacyKeychain(sign) => Some(&mut sign.submit),
+        #[cfg(feature = "ledger")]
+        CliSignWith::SignWithLedger(sign) => Some(&mut sign.submit),
+        CliSignWith::SignWithPlaintextPrivateKey(sign) => Some(&mut sign.submit),
+        CliSignWith::SignWithAccessKeyFile(sign) => Some(&mut sign.submit),
+        CliSignWith::SignWithSeedPhrase(sign) => Some(&mut sign.submit),
+        CliSignWith::SignLater(_) => None,
+    }
+}
+
+impl From<RelayerContext> for near_cli_rs::commands::ActionContext {
+    fn from(item: RelayerContext) -> Self {
+        let deploy_to_account_id = item.deploy_to_account_id.clone();
+        let signer_account_id = item.signer_account_id.clone();
+        let social_db_folder = item.social_db_folder.clone();
+        let fetch_options = item.fetch_options;
+
+        let get_prepopulated_transaction_after_getting_network_callback: near_cli_rs::commands::GetPrepopulatedTransactionAfterGettingNetworkCallback = Arc::new({
+            move |network_config| {
+                if network_config.meta_transaction_relayer_url.is_none() {
+                    color_eyre::eyre::bail!(
+                        "The network <{}> has no meta_transaction_relayer_url, pass the relayer endpoint with --relayer-url",
+                        network_config.network_name
+                    );
+                }
+                let near_social_account_id = &crate::common::get_near_social_account_id(network_config)?;
+                let mut prepopulated_transaction = near_cli_rs::commands::PrepopulatedTransaction {
+                    signer_id: signer_account_id.clone(),
+                    receiver_id: near_social_account_id.clone(),
+                    actions: vec![],
+                };
+                let Some(prepared_deploy) = super::prepare_deploy(
+                    network_config,
+                    near_social_account_id,
+                    &deploy_to_account_id,
+                    &social_db_folder,
+                    fetch_options,
+                )? else {
+                    return Ok(prepopulated_transaction);
+                };
+
+                let mut set_function_call = prepared_deploy.set_function_call;
+                // The relayer only pays for the gas, so the storage has to be prepaid on SocialDB
+                if set_function_call.deposit > 0 {
+                    color_eyre::eyre::bail!(
+                        "<{deploy_to_account_id}> needs {} more SocialDB storage to deploy the components, but a relayed deploy cannot attach a deposit. Top up the prepaid storage first, e.g. with:\n bos social-db prepaid-storage deposit {deploy_to_account_id} '{}' sign-as ...",
+                        near_cli_rs::types::near_token::NearToken::from_yoctonear(set_function_call.deposit),
+                        near_cli_rs::types::near_token::NearToken::from_yoctonear(set_function_call.deposit),
+                    );
+                }
+                set_function_call.deposit = 0;
+                prepopulated_transaction.actions = vec![
+                    near_primitives::transaction::Action::FunctionCall(Box::new(set_function_call))
+                ];
+
+                Ok(prepopulated_transaction)
+            }
+        });
+
+        let on_before_sending_transaction_callback: near_cli_rs::transaction_signature_options::OnBeforeSendingTransactionCallback = Arc::new({
+            let signed_delegate_action = item.signed_delegate_action.clone();
+            move |signed_transaction_or_signed_delegate_action, _network_config| {
+                let near_cli_rs::transaction_signature_options::SignedTransactionOrSignedDelegateAction::SignedDelegateAction(signed_delegate_action_to_send) = signed_transaction_or_signed_delegate_action else {
+                    color_eyre::eyre::bail!("A relayed deploy has to be signed as a delegate action");
+                };
+                *signed_delegate_action.lock().unwrap() = Some(signed_delegate_action_to_send.clone());
+                Ok(String::new())
+            }
+        });
+
+        Self {
+            global_context: item.global_context,
+            interacting_with_account_ids: vec![item.signer_account_id],
+            get_prepopulated_transaction_after_getting_network_callback,
+            on_before_signing_callback: Arc::new(
+                |_prepopulated_unsigned_transaction, _network_config| Ok(()),
+            ),
+            on_before_sending_transaction_callback,
+            on_after_sending_transaction_callback: Arc::new(
+                |_transaction_info, _network_config| Ok(()),
+            ),
+        }
+    }
+}
+
+impl interactive_clap::FromCli for Relayer {
+    type FromCliContext = super::DeployCmdContext;
+    type FromCliError = color_eyre::eyre::Error;
+
+    fn from_cli(
+        optional_clap_variant: Option<<Relayer as interactive_clap::ToCli>::CliVariant>,
+        context: Self::FromCliContext,
+    ) -> interactive_clap::ResultFromCli<
+        <Self as interactive_clap::ToCli>::CliVariant,
+        Self::FromCliError,
+    >
+    where
+        Self: Sized + interactive_clap::ToCli,
+    {
+        let mut clap_variant = optional_clap_variant.unwrap_or_default();
+
+        if clap_variant.signer_account_id.is_none() {
+            clap_variant.signer_account_id = match Self::input_signer_account_id(&context) {
+                Ok(Some(signer_account_id)) => Some(signer_account_id),
+                Ok(None) => return interactive_clap::ResultFromCli::Cancel(Some(clap_variant)),
+                Err(err) => return interactive_clap::ResultFromCli::Err(Some(clap_variant), err),
+            };
+        }
+        let signer_account_id = clap_variant
+            .signer_account_id
+            .clone()
+            .expect("Unexpected error");
+
+        // The network is selected before near CLI takes over, so the relayer endpoint is only
+        // set on the selected network
+        let mut cli_network_config = match clap_variant.network_config.take() {
+            Some(ClapNamedArgNetworkForTransactionArgsForRelayer::NetworkConfig(cli_arg)) => {
+                cli_arg
+            }
+            None => Default::default(),
+        };
+        if cli_network_config.network_name.is_none() {
+            cli_network_config.network_name = match near_cli_rs::common::input_network_name(
+                &context.global_context.config,
+                &[signer_account_id.clone().into()],
+            ) {
+                Ok(Some(network_name)) => Some(network_name),
+                Ok(None) => return interactive_clap::ResultFromCli::Cancel(Some(clap_variant)),
+                Err(err) => return interactive_clap::ResultFromCli::Err(Some(clap_variant), err),
+            };
+        }
+        let network_name = cli_network_config
+            .network_name
+            .clone()
+            .expect("Unexpected error");
+
+        let new_context_scope = InteractiveClapContextScopeForRelayer {
+            signer_account_id,
+            relayer_url: clap_variant.relayer_url.clone(),
+        };
+        let new_context =
+            match RelayerContext::from_previous_context(context, &new_context_scope, &network_name)
+            {
+                Ok(new_context) => new_context,
+                Err(err) => {
+                    clap_variant.network_config = Some(
+                        ClapNamedArgNetworkForTransactionArgsForRelayer::NetworkConfig(
+                            cli_network_config,
+                        ),
+                    );
+                    return interactive_clap::ResultFromCli::Err(Some(clap_variant), err);
+                }
+            };
+
+        // near CLI only displays the signed delegate action, then bos sends it to the relayer to
+        // report the resulting transaction. When `send` is selected interactively, near CLI sends
+        // it and prints the relayer response.
+        let is_relayed_by_bos = match cli_network_config
+            .transaction_signature_options
+            .as_mut()
+            .and_then(submit_mut)
+        {
+            Some(submit @ Some(near_cli_rs::transaction_signature_options::CliSubmit::Send(_))) => {
+                *submit = Some(
+                    near_cli_rs::transaction_signature_options::CliSubmit::Display(
+                        Default::default(),
+                    ),
+                );
+                true
+            }
+            _ => false,
+        };
+        let restore_send = |mut cli_network_config: near_cli_rs::network_for_transaction::CliNetworkForTransactionArgs| {
+            if is_relayed_by_bos {
+                if let Some(submit) = cli_network_config
+                    .transaction_signature_options
+                    .as_mut()
+                    .and_then(submit_mut)
+                {
+                    *submit = Some(near_cli_rs::transaction_signature_options::CliSubmit::Send(
+                        Default::default(),
+                    ));
+                }
+            }
+            ClapNamedArgNetworkForTransactionArgsForRelayer::NetworkConfig(cli_network_config)
+        };
+
+        let result = <near_cli_rs::network_for_transaction::NetworkForTransactionArgs as interactive_clap::FromCli>::from_cli(
+            Some(cli_network_config),
+            new_context.clone().into(),
+        );
+        let (optional_cli_network_config, result) = match result {
+            interactive_clap::ResultFromCli::Ok(cli_network_config) => (
+                Some(cli_network_config),
+                if is_relayed_by_bos {
+                    new_context.relay_delegate_action(&network_name)
+                } else {
+                    Ok(())
+                },
+            ),
+            interactive_clap::ResultFromCli::Cancel(optional_cli_network_config) => {
+                clap_variant.network_config = optional_cli_network_config.map(restore_send);
+                return interactive_clap::ResultFromCli::Cancel(Some(clap_variant));
+            }
+            interactive_clap::ResultFromCli::Back => return interactive_clap::ResultFromCli::Back,
+            interactive_clap::ResultFromCli::Err(optional_cli_network_config, err) => {
+                (optional_cli_network_config, Err(err))
+            }
+        };
+        clap_variant.network_config = optional_cli_network_config.map(restore_send);
+        match result {
+            Ok(()) => interactive_clap::ResultFromCli::Ok(clap_variant),
+            Err(err) => interactive_clap::ResultFromCli::Err(Some(clap_variant), err),
+        }
+    }
+}
+
+impl Relayer {
+    fn input_signer_account_id(
+        context: &super::DeployCmdContext,
+    ) -> color_eyre::eyre::Result<Option<near_cli_rs::types::account_id::AccountId>> {
+        Ok(Some(
+            CustomType::new("Which account signs the delegate action?")
+                .with_default(context.deploy_to_account_id.clone())
+                .prompt()?,
+        ))
+    }
+}
@@ -1,7 +1,5 @@
-use std::collections::HashMap;
 use std::sync::Arc;
 
-use color_eyre::eyre::{ContextCompat, WrapErr};
 use inquire::{CustomType, Select};
 
 #[derive(Debug, Clone, interactive_clap::InteractiveClap)]
@@ -11,6 +9,14 @@ pub struct Signer {
     #[interactive_clap(skip_default_input_arg)]
     /// What is the signer account ID?
     signer_account_id: near_cli_rs::types::account_id::AccountId,
+    /// Write the unsigned transaction to this file instead of signing it (to sign it offline)
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    export_unsigned_transaction: Option<near_cli_rs::types::path_buf::PathBuf>,
+    /// The public key of the signer access key that will sign the exported transaction
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    signer_public_key: Option<near_cli_rs::types::public_key::PublicKey>,
     #[interactive_clap(named_arg)]
     /// Select network
     network_config: near_cli_rs::network_for_transaction::NetworkForTransactionArgs,
@@ -20,8 +26,10 @@ pub struct Signer {
 pub struct SignerContext {
     global_context: near_cli_rs::GlobalContext,
     social_db_folder: String,
+    fetch_options: crate::api::FetchOptions,
     deploy_to_account_id: near_primitives::types::AccountId,
     signer_account_id: near_primitives::types::AccountId,
+    export_unsigned_transaction: Option<(std::path::PathBuf, near_crypto::PublicKey)>,
 }
 
 impl SignerContext {
@@ -29,11 +37,21 @@ impl SignerContext {
         previous_context: super::DeployCmdContext,
         scope: &<Signer as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
     ) -> color_eyre::eyre::Result<Self> {
+        let export_unsigned_transaction =
+            match (&scope.export_unsigned_transaction, &scope.signer_public_key) {
+                (Some(path), Some(public_key)) => Some((path.0.clone(), public_key.0.clone())),
+                (Some(_), None) => color_eyre::eyre::bail!(
+                    "--signer-public-key is required to export the unsigned transaction"
+                ),
+                (None, _) => None,
+            };
         Ok(Self {
             global_context: previous_context.global_context,
             social_db_folder: previous_context.social_db_folder,
+            fetch_options: previous_context.fetch_options,
             deploy_to_account_id: previous_context.deploy_to_account_id.into(),
             signer_account_id: scope.signer_account_id.clone().into(),
+            export_unsigned_transaction,
         })
     }
 }
@@ -44,102 +62,6 @@ impl From<SignerContext> for near_cli_rs::commands::ActionContext {
         let signer_id = item.signer_account_id.clone();
         let social_db_folder = item.social_db_folder.clone();
 
-        let get_prepopulated_transaction_after_getting_network_callback: near_cli_rs::commands::GetPrepopulatedTransactionAfterGettingNetworkCallback = Arc::new({
-            move |network_config| {
-                let near_social_account_id = crate::consts::NEAR_SOCIAL_ACCOUNT_ID.get(network_config.network_name.as_str())
-                    .wrap_err_with(|| format!("The <{}> network does not have a near-social contract.", network_config.network_name))?;
-                let mut prepopulated_transaction = near_cli_rs::commands::PrepopulatedTransaction {
-                    signer_id: signer_id.clone(),
-                    receiver_id: near_social_account_id.clone(),
-                    actions: vec![],
-                };
-                let local_components = crate::common::get_local_components()?;
-                if local_components.is_empty() {
-                    println!("There are no components in the current ./src folder. Goodbye.");
-                    return Ok(prepopulated_transaction);
-                }
-                let local_component_name_list = local_components.keys().collect::<Vec<_>>();
-                let remote_components = crate::common::get_remote_components(
-                    network_config,
-                    local_component_name_list,
-                    near_social_account_id,
-                    &deploy_to_account_id,
-                    &item.social_db_folder
-                )?;
-
-                let components_to_deploy =
-                    if !remote_components.is_empty() {
-                        let updated_components = crate::common::get_updated_components(local_components, &remote_components);
-                        if updated_components.is_empty() {
-                            println!("There are no new or modified components in the current ./src folder. Goodbye.");
-                            return Ok(prepopulated_transaction);
-                        }
-                        updated_components
-                    } else {
-                        println!("\nAll local components will be deployed to <{deploy_to_account_id}> as new.");
-                        local_components
-                    };
-
-                let new_social_db_state = crate::socialdb_types::SocialDb {
-                    accounts: HashMap::from([(
-                        deploy_to_account_id.clone(),
-                        crate::socialdb_types::SocialDbComponentKey {
-                            key: HashMap::from([(
-                                item.social_db_folder.clone(),
-                                crate::socialdb_types::SocialDbAccountMetadata {
-                                    components: components_to_deploy
-                                }
-                            )])
-                        }
-                    )])
-                };
-                let new_social_db_state_json = serde_json::json!(&new_social_db_state);
-                let remote_social_db_state_json = serde_json::json!(&crate::socialdb_types::SocialDb {
-                    accounts: HashMap::from([(
-                        deploy_to_account_id.clone(),
-                        crate::socialdb_types::SocialDbComponentKey {
-                            key: HashMap::from([(
-                                item.social_db_folder.clone(),
-                                crate::socialdb_types::SocialDbAccountMetadata {
-                                    components: remote_components
-                                }
-                            )])
-                        }
-                    )])
-                });
-
-                let args = serde_json::to_string(&super::TransactionFunctionArgs {
-                    data: new_social_db_state,
-                })?
-                .into_bytes();
-
-                let json_rpc_client = network_config.json_rpc_client();
-
-                let deposit = tokio::runtime::Runtime::new().unwrap().block_on(
-                    near_socialdb_client::required_deposit(
-                        &json_rpc_client,
-                        near_social_account_id,
-                        &deploy_to_account_id,
-                        &new_social_db_state_json,
-                        Some(&remote_social_db_state_json),
-                    )
-                )?;
-
-                prepopulated_transaction.actions = vec![
-                    near_primitives::transaction::Action::FunctionCall(
-                        Box::new(near_primitives::transaction::FunctionCallAction {
-                            method_name: "set".to_string(),
-                            args,
-                            gas: near_cli_rs::common::NearGas::from_tgas(300).as_gas(),
-                            deposit: deposit.as_yoctonear(),
-                        }),
-                    )
-                ];
-
-                Ok(prepopulated_transaction)
-            }
-        });
-
         let db_prefix = social_db_folder.clone();
         let on_before_signing_callback: near_cli_rs::commands::OnBeforeSigningCallback =
             Arc::new({
@@ -152,9 +74,8 @@ impl From<SignerContext> for near_cli_rs::commands::ActionContext {
                     if let Some(near_primitives::transaction::Action::FunctionCall(action)) =
                         prepopulated_unsigned_transaction.actions.get_mut(0)
                     {
-                        action.deposit = tokio::runtime::Runtime::new()
-                            .unwrap()
-                            .block_on(near_socialdb_client::get_deposit(
+                        action.deposit =
+                            crate::common::block_on(near_socialdb_client::get_deposit(
                                 &json_rpc_client,
                                 &signer_account_id,
                                 &public_key,
@@ -174,38 +95,54 @@ impl From<SignerContext> for near_cli_rs::commands::ActionContext {
                 }
             });
 
-        let on_after_sending_transaction_callback: near_cli_rs::transaction_signature_options::OnAfterSendingTransactionCallback = Arc::new({
-            move |transaction_info, _network_config| {
-                let args = if let near_primitives::views::FinalExecutionStatus::SuccessValue(_) = transaction_info.status {
-                    if let near_primitives::views::ActionView::FunctionCall { args, .. } =
-                        &transaction_info.transaction.actions[0]
-                    {
-                        args
-                    } else {
-                        color_eyre::eyre::bail!(
-                            "Internal error: Unexpected function call arguments",
-                        );
-                    }
-                } else {
-                    color_eyre::eyre::bail!("Components deployment failed!");
+        let get_prepopulated_transaction_after_getting_network_callback: near_cli_rs::commands::GetPrepopulatedTransactionAfterGettingNetworkCallback = Arc::new({
+            let on_before_signing_callback = on_before_signing_callback.clone();
+            let config = item.global_context.config.clone();
+            move |network_config| {
+                let near_social_account_id = &crate::common::get_near_social_account_id(network_config)?;
+                let mut prepopulated_transaction = near_cli_rs::commands::PrepopulatedTransaction {
+                    signer_id: signer_id.clone(),
+                    receiver_id: near_social_account_id.clone(),
+                    actions: vec![],
+                };
+                let Some(prepared_deploy) = super::prepare_deploy(
+                    network_config,
+                    near_social_account_id,
+                    &deploy_to_account_id,
+                    &item.social_db_folder,
+                    item.fetch_options,
+                )? else {
+                    return Ok(prepopulated_transaction);
                 };
 
-                let transaction_function_args: super::TransactionFunctionArgs =
-                    serde_json::from_slice(args).wrap_err("Internal error: Could not parse SocialDB request that we just created.")?;
-
-                let social_account_metadata = transaction_function_args.data.accounts.get(&item.deploy_to_account_id)
-                    .wrap_err("Internal error: Could not get the key for the component from SocialDB request that we just created.")?
-                    .key
-                    .get(&db_prefix)
-                    .wrap_err("Internal error: Could not get metadata from SocialDB request that we just created.")?;
-                let updated_components = &social_account_metadata.components;
+                prepopulated_transaction.actions = vec![
+                    near_primitives::transaction::Action::FunctionCall(Box::new(prepared_deploy.set_function_call))
+                ];
 
-                println!("\n<{}> components were successfully deployed to <{}>/{db_prefix}/:", updated_components.len(), item.deploy_to_account_id);
-                for component in updated_components.keys() {
-                    println!(" * {component}")
+                if let Some((path, signer_public_key)) = &item.export_unsigned_transaction {
+                    crate::transaction::unsigned::export(
+                        path,
+                        &config,
+                        network_config,
+                        &prepopulated_transaction,
+                        signer_public_key,
+                        &on_before_signing_callback,
+                        crate::transaction::unsigned::Operation::ComponentsDeploy {
+                            deploy_to_account_id: deploy_to_account_id.clone(),
+                            social_db_folder: item.social_db_folder.clone(),
+                        },
+                    )?;
+                    prepopulated_transaction.actions = vec![];
                 }
-                println!();
-                Ok(())
+
+                Ok(prepopulated_transaction)
+            }
+        });
+
+        let on_after_sending_transaction_callback: near_cli_rs::transaction_signature_options::OnAfterSendingTransactionCallback = Arc::new({
+            move |transaction_info, _network_config| {
+                crate::cache::invalidate_final_block_entries();
+                crate::common::print_deployed_components(transaction_info, &item.deploy_to_account_id, &db_prefix)
             }
         });
 
@@ -235,7 +172,7 @@ impl Signer {
                 &context.global_context.config.network_connection,
                 signer_account_id.clone().into(),
             ) {
-                println!("\nThe account <{signer_account_id}> does not yet exist.");
+                crate::human_println!("\nThe account <{signer_account_id}> does not yet exist.");
                 #[derive(strum_macros::Display)]
                 enum ConfirmOptions {
                     #[strum(to_string = "Yes, I want to enter a new account name.")]
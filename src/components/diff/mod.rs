@@ -1,5 +1,3 @@
-use color_eyre::eyre::ContextCompat;
-
 #[derive(Debug, Clone, interactive_clap::InteractiveClap)]
 #[interactive_clap(input_context = super::ComponentsContext)]
 #[interactive_clap(output_context = DiffCmdContext)]
@@ -25,18 +23,14 @@ impl DiffCmdContext {
             std::sync::Arc::new({
                 let account_id = account_id.clone();
                 move |network_config| {
-                    let near_social_account_id = crate::consts::NEAR_SOCIAL_ACCOUNT_ID
-                        .get(network_config.network_name.as_str())
-                        .wrap_err_with(|| {
-                            format!(
-                                "The <{}> network does not have a near-social contract.",
-                                network_config.network_name
-                            )
-                        })?;
+                    let near_social_account_id =
+                        &crate::common::get_near_social_account_id(network_config)?;
 
                     let local_components = crate::common::get_local_components()?;
                     if local_components.is_empty() {
-                        println!("There are no components in the current ./src folder. Goodbye.");
+                        crate::human_println!(
+                            "There are no components in the current ./src folder. Goodbye."
+                        );
                         return Ok(());
                     }
                     let local_component_name_list = local_components.keys().collect::<Vec<_>>();
@@ -47,21 +41,47 @@ impl DiffCmdContext {
                         near_social_account_id,
                         &account_id,
                         &previous_context.social_db_folder,
+                        previous_context.fetch_options,
+                        crate::cache::CachePolicy::Use,
                     )?;
 
+                    let plan =
+                        crate::api::plan_deploy(local_components.clone(), &remote_components);
                     if !remote_components.is_empty() {
                         let updated_components = crate::common::get_updated_components(
                             local_components,
                             &remote_components,
                         );
                         if updated_components.is_empty() {
-                            println!("There are no new or modified components in the current ./src folder. Goodbye.");
-                            return Ok(());
+                            crate::human_println!("There are no new or modified components in the current ./src folder. Goodbye.");
                         }
                     } else {
-                        println!("\nAll local components are new to <{account_id}>.");
+                        crate::human_println!("\nAll local components are new to <{account_id}>.");
+                    };
+
+                    let sorted_names = |components: &crate::api::Components| {
+                        components
+                            .keys()
+                            .cloned()
+                            .collect::<std::collections::BTreeSet<_>>()
                     };
-                    Ok(())
+                    crate::output::emit(&serde_json::json!({
+                        "account_id": account_id,
+                        "new": sorted_names(&plan.new),
+                        "updated": plan
+                            .updated
+                            .iter()
+                            .map(|(component_name, component)| {
+                                let diff = crate::api::component_diff(
+                                    component_name,
+                                    remote_components.get(component_name),
+                                    component,
+                                );
+                                (component_name.clone(), diff)
+                            })
+                            .collect::<std::collections::BTreeMap<_, _>>(),
+                        "unchanged": sorted_names(&plan.unchanged),
+                    }))
                 }
             });
         Ok(Self(near_cli_rs::network::NetworkContext {
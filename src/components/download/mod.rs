@@ -1,5 +1,5 @@
 use color_eyre::eyre::{ContextCompat, WrapErr};
-use near_cli_rs::common::{CallResultExt, JsonRpcClientExt};
+use near_cli_rs::common::CallResultExt;
 
 #[derive(Debug, Clone, interactive_clap::InteractiveClap)]
 #[interactive_clap(input_context = super::ComponentsContext)]
@@ -26,35 +26,27 @@ impl DownloadCmdContext {
         let on_after_getting_network_callback: near_cli_rs::network::OnAfterGettingNetworkCallback =
             std::sync::Arc::new({
                 move |network_config| {
-                    let near_social_account_id = match crate::consts::NEAR_SOCIAL_ACCOUNT_ID
-                        .get(&network_config.network_name.as_str())
-                    {
-                        Some(account_id) => account_id,
-                        None => {
-                            return Err(color_eyre::Report::msg(format!(
-                                "The <{}> network does not have a near-social contract.",
-                                network_config.network_name
-                            )))
-                        }
-                    };
+                    let near_social_account_id =
+                        &crate::common::get_near_social_account_id(network_config)?;
 
                     let input_args = serde_json::to_string(&crate::socialdb_types::SocialDbQuery {
                         keys: vec![format!(
                             "{account_id}/{}/*",
                             previous_context.social_db_folder
                         )],
+                        options: None,
                     })
                     .wrap_err("Internal error: could not serialize SocialDB input args")?;
 
-                    let call_result = network_config
-                        .json_rpc_client()
-                        .blocking_call_view_function(
-                            near_social_account_id,
-                            "keys",
-                            input_args.into_bytes(),
-                            near_primitives::types::Finality::Final.into(),
-                        )
-                        .wrap_err("Failed to fetch the components state from SocialDB")?;
+                    let call_result = crate::common::call_view_function_with_failover(
+                        network_config,
+                        near_social_account_id,
+                        "keys",
+                        input_args.into_bytes(),
+                        near_primitives::types::Finality::Final.into(),
+                        crate::cache::CachePolicy::Use,
+                    )
+                    .wrap_err("Failed to fetch the components state from SocialDB")?;
                     let keys: SocialDbKeys = call_result.parse_result_from_json()?;
 
                     let remote_social_account_components = if let Some(component_key) =
@@ -65,16 +57,22 @@ impl DownloadCmdContext {
                         {
                             account_components
                         } else {
-                            println!(
+                            crate::human_println!(
                                     "\nThere are currently no components in the account <{account_id}>.",
                                 );
-                            return Ok(());
+                            return crate::output::emit(&serde_json::json!({
+                                "account_id": account_id,
+                                "files": [],
+                            }));
                         }
                     } else {
-                        println!(
+                        crate::human_println!(
                             "\nThere are currently no components in the account <{account_id}>.",
                         );
-                        return Ok(());
+                        return crate::output::emit(&serde_json::json!({
+                            "account_id": account_id,
+                            "files": [],
+                        }));
                     };
                     let remote_component_name_list = remote_social_account_components
                         .components
@@ -86,9 +84,12 @@ impl DownloadCmdContext {
                         near_social_account_id,
                         &account_id,
                         &previous_context.social_db_folder,
+                        previous_context.fetch_options,
+                        crate::cache::CachePolicy::Use,
                     )?;
 
                     let components_src_folder = std::path::PathBuf::from("./src");
+                    let mut downloaded_files = vec![];
                     for (component_name, component) in remote_components.iter() {
                         let mut component_path = components_src_folder.clone();
                         component_path.extend(component_name.split('.'));
@@ -106,6 +107,7 @@ impl DownloadCmdContext {
                                     component_code_path.display()
                                 )
                             })?;
+                        downloaded_files.push(component_code_path);
                         if let Some(metadata) = component.metadata() {
                             let metadata =
                                 serde_json::to_string_pretty(metadata).wrap_err_with(|| {
@@ -120,16 +122,21 @@ impl DownloadCmdContext {
                                         component_metadata_path.display()
                                     )
                                 })?;
+                            downloaded_files.push(component_metadata_path);
                         }
                     }
 
-                    println!(
+                    crate::human_println!(
                         "Components for account <{}> were downloaded into <{}> successfully",
                         account_id,
                         components_src_folder.display()
                     );
 
-                    Ok(())
+                    downloaded_files.sort();
+                    crate::output::emit(&serde_json::json!({
+                        "account_id": account_id,
+                        "files": downloaded_files,
+                    }))
                 }
             });
         Ok(Self(near_cli_rs::network::NetworkContext {
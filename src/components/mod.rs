@@ -1,9 +1,11 @@
 use strum::{EnumDiscriminants, EnumIter, EnumMessage};
 
+mod apply;
 mod delete;
 mod deploy;
 mod diff;
 mod download;
+mod plan;
 
 #[derive(Debug, Clone, interactive_clap::InteractiveClap)]
 #[interactive_clap(input_context = near_cli_rs::GlobalContext)]
@@ -13,6 +15,14 @@ pub struct Components {
     #[interactive_clap(long)]
     #[interactive_clap(skip_interactive_input)]
     social_db_folder: Option<String>,
+    /// Number of components fetched with a single SocialDB `get` call (default: 15)
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    fetch_chunk_size: Option<u64>,
+    /// Number of SocialDB `get` calls running at the same time (default: 10)
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    fetch_concurrency: Option<u64>,
     #[interactive_clap(subcommand)]
     command: self::ComponentsCommand,
 }
@@ -21,6 +31,7 @@ pub struct Components {
 pub struct ComponentsContext {
     pub global_context: near_cli_rs::GlobalContext,
     pub social_db_folder: String,
+    pub fetch_options: crate::api::FetchOptions,
 }
 
 impl ComponentsContext {
@@ -28,12 +39,26 @@ impl ComponentsContext {
         previous_context: near_cli_rs::GlobalContext,
         scope: &<Components as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
     ) -> color_eyre::eyre::Result<Self> {
+        let default_fetch_options = crate::api::FetchOptions::default();
         Ok(Self {
             global_context: previous_context,
             social_db_folder: scope
                 .social_db_folder
                 .clone()
                 .unwrap_or("widget".to_owned()),
+            fetch_options: crate::api::FetchOptions {
+                chunk_size: scope
+                    .fetch_chunk_size
+                    .map_or(default_fetch_options.chunk_size, |chunk_size| {
+                        chunk_size as usize
+                    }),
+                concurrency: scope
+                    .fetch_concurrency
+                    .map_or(default_fetch_options.concurrency, |concurrency| {
+                        concurrency as usize
+                    }),
+                ..default_fetch_options
+            },
         })
     }
 }
@@ -56,6 +81,16 @@ pub enum ComponentsCommand {
     ))]
     /// Deploy сomponents if code has changed
     Deploy(self::deploy::DeployCmd),
+    #[strum_discriminants(strum(
+        message = "plan        -   Write a reviewable deploy plan for the changed components"
+    ))]
+    /// Write a reviewable deploy plan for the changed components
+    Plan(self::plan::PlanCmd),
+    #[strum_discriminants(strum(
+        message = "apply       -   Deploy a plan if the deployed components have not changed"
+    ))]
+    /// Deploy a plan if the deployed components have not changed
+    Apply(self::apply::ApplyCmd),
     #[strum_discriminants(strum(message = "delete      -   Delete components from account"))]
     /// Delete components from account
     Delete(self::delete::DeleteCmd),
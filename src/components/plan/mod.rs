@@ -0,0 +1,146 @@
+use color_eyre::eyre::WrapErr;
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = super::ComponentsContext)]
+#[interactive_clap(output_context = PlanCmdContext)]
+pub struct PlanCmd {
+    #[interactive_clap(skip_default_input_arg)]
+    /// Which account do you want to plan the deployment to?
+    account_id: near_cli_rs::types::account_id::AccountId,
+    /// Where to write the plan (default: ./deploy-plan.json)
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    plan_file: Option<near_cli_rs::types::path_buf::PathBuf>,
+    #[interactive_clap(named_arg)]
+    /// Select network
+    network_config: near_cli_rs::network::Network,
+}
+
+#[derive(Clone)]
+pub struct PlanCmdContext(near_cli_rs::network::NetworkContext);
+
+impl PlanCmdContext {
+    pub fn from_previous_context(
+        previous_context: super::ComponentsContext,
+        scope: &<PlanCmd as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let account_id: near_primitives::types::AccountId = scope.account_id.clone().into();
+        let plan_file = scope
+            .plan_file
+            .clone()
+            .map_or_else(|| "deploy-plan.json".into(), |plan_file| plan_file.0);
+        let on_after_getting_network_callback: near_cli_rs::network::OnAfterGettingNetworkCallback =
+            std::sync::Arc::new({
+                let account_id = account_id.clone();
+                move |network_config| {
+                    let near_social_account_id =
+                        &crate::common::get_near_social_account_id(network_config)?;
+
+                    let local_components = crate::common::get_local_components()?;
+                    if local_components.is_empty() {
+                        crate::human_println!(
+                            "There are no components in the current ./src folder. Goodbye."
+                        );
+                        return Ok(());
+                    }
+
+                    let block = crate::common::block_on(
+                        crate::rpc::RpcEndpoints::for_network(network_config).call(
+                            &near_jsonrpc_client::methods::query::RpcQueryRequest {
+                                block_reference: near_primitives::types::Finality::Final.into(),
+                                request: near_primitives::views::QueryRequest::ViewAccount {
+                                    account_id: account_id.clone(),
+                                },
+                            },
+                        ),
+                    )
+                    .wrap_err_with(|| format!("Failed to fetch the account <{account_id}>"))?;
+
+                    // The plan is verified against the current state, so it must not be made from a cached response
+                    let remote_components = crate::common::get_remote_components(
+                        network_config,
+                        local_components.keys().collect(),
+                        near_social_account_id,
+                        &account_id,
+                        &previous_context.social_db_folder,
+                        previous_context.fetch_options,
+                        crate::cache::CachePolicy::Bypass,
+                    )?;
+
+                    let Some(plan) = crate::common::block_on(crate::api::DeployPlanFile::new(
+                        &network_config.json_rpc_client(),
+                        &network_config.network_name,
+                        near_social_account_id,
+                        &account_id,
+                        &previous_context.social_db_folder,
+                        (block.block_height, block.block_hash),
+                        local_components,
+                        remote_components,
+                    ))?
+                    else {
+                        crate::human_println!("There are no new or modified components in the current ./src folder. Goodbye.");
+                        return Ok(());
+                    };
+
+                    std::fs::write(&plan_file, serde_json::to_string_pretty(&plan)? + "\n")
+                        .wrap_err_with(|| {
+                            format!("Failed to write the plan to {}", plan_file.display())
+                        })?;
+
+                    crate::human_println!(
+                        "\nThe plan to deploy <{}> components to <{account_id}>/{}/ at block #{} was written to {}:",
+                        plan.components.len(),
+                        plan.social_db_folder,
+                        plan.block_height,
+                        plan_file.display()
+                    );
+                    for (component_name, planned_component) in &plan.components {
+                        let change = match planned_component.change {
+                            crate::api::ComponentChange::New => "new",
+                            crate::api::ComponentChange::Updated => "updated",
+                        };
+                        crate::human_println!(" * {component_name} ({change})");
+                    }
+                    crate::human_println!(
+                        "\nRequired storage deposit: {}\nApply it with:\n bos components apply {} sign-as {account_id} network-config {}",
+                        plan.deposit,
+                        plan_file.display(),
+                        network_config.network_name
+                    );
+                    crate::output::emit(&serde_json::json!({
+                        "plan_file": plan_file,
+                        "account_id": account_id,
+                        "block_height": plan.block_height,
+                        "deposit": plan.deposit,
+                        "components": plan
+                            .components
+                            .iter()
+                            .map(|(component_name, planned_component)| (component_name, planned_component.change))
+                            .collect::<std::collections::BTreeMap<_, _>>(),
+                    }))
+                }
+            });
+        Ok(Self(near_cli_rs::network::NetworkContext {
+            config: previous_context.global_context.config,
+            interacting_with_account_ids: vec![account_id],
+            on_after_getting_network_callback,
+        }))
+    }
+}
+
+impl From<PlanCmdContext> for near_cli_rs::network::NetworkContext {
+    fn from(item: PlanCmdContext) -> Self {
+        item.0
+    }
+}
+
+impl PlanCmd {
+    pub fn input_account_id(
+        context: &super::ComponentsContext,
+    ) -> color_eyre::eyre::Result<Option<near_cli_rs::types::account_id::AccountId>> {
+        near_cli_rs::common::input_non_signer_account_id_from_used_account_list(
+            &context.global_context.config.credentials_home_dir,
+            "Which account do you want to plan the deployment to?",
+        )
+    }
+}
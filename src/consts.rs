@@ -15,3 +15,11 @@ lazy_static! {
             ),
         ]);
 }
+
+/// Storage staking cost of a single byte on NEAR (10^19 yoctoNEAR per byte, i.e. 1 NEAR per 100 kb)
+pub const STORAGE_COST_PER_BYTE: u128 = 10u128.pow(19);
+
+/// The storage overhead SocialDB adds for every key and every node of the data (see `estimate_data_size` in
+/// https://github.com/NearSocial/VM/blob/24055641b53e7eeadf6efdb9c073f85f02463798/src/lib/data/utils.js#L182-L198)
+pub const ESTIMATED_KEY_VALUE_SIZE: usize = 40 * 3 + 8 + 12;
+pub const ESTIMATED_NODE_SIZE: usize = 40 * 2 + 8 + 10;
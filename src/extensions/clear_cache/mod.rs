@@ -0,0 +1,21 @@
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = near_cli_rs::GlobalContext)]
+#[interactive_clap(output_context = ClearCacheCommandContext)]
+pub struct ClearCacheCommand;
+
+#[derive(Debug, Clone)]
+pub struct ClearCacheCommandContext;
+
+impl ClearCacheCommandContext {
+    pub fn from_previous_context(
+        _previous_context: near_cli_rs::GlobalContext,
+        _scope: &<ClearCacheCommand as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let removed_entries = crate::cache::clear()?;
+        eprintln!(
+            "Removed {removed_entries} cached SocialDB responses from {}",
+            crate::cache::cache_dir()?.display()
+        );
+        Ok(Self)
+    }
+}
@@ -1,5 +1,6 @@
 use strum::{EnumDiscriminants, EnumIter, EnumMessage};
 
+mod clear_cache;
 pub mod self_update;
 
 #[derive(Debug, Clone, interactive_clap::InteractiveClap)]
@@ -18,4 +19,9 @@ pub enum ExtensionsActions {
     #[strum_discriminants(strum(message = "self-update   -  Self update bos CLI"))]
     /// Self update bos CLI
     SelfUpdate(self::self_update::SelfUpdateCommand),
+    #[strum_discriminants(strum(
+        message = "clear-cache   -  Remove the cached SocialDB responses"
+    ))]
+    /// Remove the cached SocialDB responses
+    ClearCache(self::clear_cache::ClearCacheCommand),
 }
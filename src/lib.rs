@@ -0,0 +1,12 @@
+//! Library behind the `bos` CLI for developing BOS components stored in SocialDB.
+//!
+//! The [`api`] module provides an async API to load local components, fetch the deployed ones,
+//! compute a deploy plan and build the SocialDB `set` function call, so the same flow can be
+//! driven from Rust code without going through the CLI.
+pub mod api;
+pub mod cache;
+pub mod common;
+pub mod consts;
+pub mod output;
+pub mod rpc;
+pub mod socialdb_types;
@@ -16,13 +16,16 @@ use tracing_subscriber::layer::SubscriberExt;
 use tracing_subscriber::util::SubscriberInitExt;
 use tracing_subscriber::EnvFilter;
 
-pub mod common;
+use bos_cli::{
+    api, cache, common, consts, human_print, human_println, output, rpc, socialdb_types,
+};
+
 mod components;
-pub mod consts;
 mod extensions;
 mod project;
+mod sandbox;
 mod social_db;
-pub mod socialdb_types;
+mod transaction;
 
 /// near-cli is a toolbox for interacting with NEAR protocol
 
@@ -35,6 +38,21 @@ struct Cmd {
     /// TEACH-ME mode
     #[interactive_clap(long)]
     teach_me: bool,
+    /// Do not use the local SocialDB response cache
+    #[interactive_clap(long)]
+    no_cache: bool,
+    /// Use this SocialDB contract account instead of the one configured for the network
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    social_db_contract: Option<near_cli_rs::types::account_id::AccountId>,
+    /// Fallback RPC endpoints tried in order when the configured one fails (comma-separated <network-name>=<rpc-url> entries)
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    rpc_fallback_urls: Option<near_cli_rs::types::vec_string::VecString>,
+    /// Output format: human (default) or json (a single JSON result on stdout, messages on stderr)
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    output: Option<self::output::OutputFormat>,
     #[interactive_clap(subcommand)]
     command: self::Command,
 }
@@ -56,13 +74,23 @@ pub enum Command {
     #[strum_discriminants(strum(message = "socialdb     -   SocialDb management"))]
     /// SocialDb management
     SocialDb(self::social_db::SocialDb),
+    #[strum_discriminants(strum(
+        message = "sandbox      -   Local sandbox node setup (SocialDB deployment, test accounts)"
+    ))]
+    /// Local sandbox node setup (SocialDB deployment, test accounts)
+    Sandbox(self::sandbox::Sandbox),
     #[strum_discriminants(strum(message = "extension    -   Manage bos CLI and extensions"))]
     /// Manage bos CLI and extensions
     Extensions(self::extensions::Extensions),
+    #[strum_discriminants(strum(
+        message = "transaction  -   Broadcast transactions signed offline"
+    ))]
+    /// Broadcast transactions signed offline
+    Transaction(self::transaction::Transaction),
 }
 
 fn main() -> CliResult {
-    let config = Config::get_config_toml()?;
+    let mut config = Config::get_config_toml()?;
 
     #[cfg(not(debug_assertions))]
     let display_env_section = false;
@@ -124,6 +152,35 @@ fn main() -> CliResult {
             .init();
     };
 
+    if let Some(social_db_contract) = &cli.social_db_contract {
+        for (_, network_config) in config.network_connection.iter_mut() {
+            network_config.near_social_db_contract_account_id =
+                Some(social_db_contract.clone().into());
+        }
+    }
+
+    if cli.no_cache {
+        self::cache::disable();
+    }
+    self::output::set_format(cli.output.unwrap_or_default());
+
+    if let Some(rpc_fallback_urls) = &cli.rpc_fallback_urls {
+        self::rpc::set_fallback_rpc_urls(self::rpc::parse_fallback_rpc_urls(&rpc_fallback_urls.0)?);
+        // near CLI sends the transactions through the `rpc_url` of the network connection, so the
+        // connection selected on the command line gets the healthy endpoint (the reads select
+        // their endpoint on the first request)
+        let cli_args = cli.to_cli_args();
+        let selected_connection_name = cli_args
+            .iter()
+            .position(|arg| arg == "network-config")
+            .and_then(|index| cli_args.get(index + 1));
+        if let Some(network_config) = selected_connection_name
+            .and_then(|connection_name| config.network_connection.get_mut(connection_name))
+        {
+            self::rpc::select_healthy_rpc_url(network_config);
+        }
+    }
+
     let verbosity = match (cli.quiet, cli.teach_me) {
         (true, _) => Verbosity::Quiet,
         (false, true) => Verbosity::TeachMe,
@@ -158,7 +215,7 @@ fn main() -> CliResult {
             unreachable!("The Command does not have back option")
         }
         interactive_clap::ResultFromCli::Err(optional_cli_cmd, err) => {
-            if let Some(cli_cmd) = optional_cli_cmd {
+            if let Some(cli_cmd) = &optional_cli_cmd {
                 eprintln!(
                     "Your console command:\n{} {}",
                     bos_exec_path.yellow(),
@@ -168,6 +225,7 @@ fn main() -> CliResult {
             Err(err)
         }
     };
+    self::output::finish(&cli_cmd);
 
     let handle = std::thread::spawn(|| -> color_eyre::eyre::Result<String> {
         self::extensions::self_update::get_latest_version()
@@ -202,6 +260,10 @@ fn main() -> CliResult {
                 let self_update_cli_cmd = CliCmd {
                     quiet: false,
                     teach_me: false,
+                    no_cache: false,
+                    social_db_contract: None,
+                    rpc_fallback_urls: None,
+                    output: None,
                     command: Some(self::CliCommand::Extensions(
                         self::extensions::CliExtensions {
                             extensions_actions: Some(
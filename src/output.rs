@@ -0,0 +1,104 @@
+//! The global `--output` mode.
+//!
+//! In the `json` mode every command prints a single JSON document with its result to stdout
+//! (see [`emit`]), and the human-readable messages printed with [`human_println!`] and
+//! [`human_print!`] go to stderr instead, so stdout can be piped to `jq` and other tools.
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static JSON: AtomicBool = AtomicBool::new(false);
+static EMITTED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    #[default]
+    Human,
+    Json,
+}
+
+impl std::fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Human => write!(f, "human"),
+            Self::Json => write!(f, "json"),
+        }
+    }
+}
+
+impl std::str::FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "human" => Ok(Self::Human),
+            "json" => Ok(Self::Json),
+            _ => Err(format!(
+                "Unknown output format <{s}>, expected <human> or <json>"
+            )),
+        }
+    }
+}
+
+impl interactive_clap::ToCli for OutputFormat {
+    type CliVariant = OutputFormat;
+}
+
+pub fn set_format(format: OutputFormat) {
+    JSON.store(format == OutputFormat::Json, Ordering::Relaxed);
+}
+
+pub fn is_json() -> bool {
+    JSON.load(Ordering::Relaxed)
+}
+
+/// Prints the result of the command to stdout in the `json` mode (and does nothing otherwise).
+///
+/// Each command emits its result once, after it has succeeded.
+pub fn emit(result: &impl serde::Serialize) -> color_eyre::eyre::Result<()> {
+    if is_json() {
+        println!("{}", serde_json::to_string_pretty(result)?);
+        EMITTED.store(true, Ordering::Relaxed);
+    }
+    Ok(())
+}
+
+/// Makes sure the `json` mode prints exactly one JSON document: the error of a failed command, or
+/// `{"status": "ok"}` for commands that have no result to report (e.g. when there was nothing
+/// to deploy).
+pub fn finish<T>(result: &color_eyre::eyre::Result<T>) {
+    if !is_json() || EMITTED.load(Ordering::Relaxed) {
+        return;
+    }
+    let document = match result {
+        Ok(_) => serde_json::json!({ "status": "ok" }),
+        Err(err) => serde_json::json!({
+            "status": "error",
+            "error": err.to_string(),
+            "causes": err.chain().skip(1).map(ToString::to_string).collect::<Vec<_>>(),
+        }),
+    };
+    println!("{document:#}");
+}
+
+/// Like `println!`, but prints to stderr in the `json` mode.
+#[macro_export]
+macro_rules! human_println {
+    ($($arg:tt)*) => {
+        if $crate::output::is_json() {
+            eprintln!($($arg)*)
+        } else {
+            println!($($arg)*)
+        }
+    };
+}
+
+/// Like `print!`, but prints to stderr in the `json` mode.
+#[macro_export]
+macro_rules! human_print {
+    ($($arg:tt)*) => {
+        if $crate::output::is_json() {
+            eprint!($($arg)*)
+        } else {
+            print!($($arg)*)
+        }
+    };
+}
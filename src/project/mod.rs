@@ -1,6 +1,7 @@
 use strum::{EnumDiscriminants, EnumIter, EnumMessage};
 
 mod new;
+mod setup_ci_key;
 
 #[derive(Debug, Clone, interactive_clap::InteractiveClap)]
 #[interactive_clap(context = near_cli_rs::GlobalContext)]
@@ -14,7 +15,12 @@ pub struct Project {
 #[strum_discriminants(derive(EnumMessage, EnumIter))]
 /// What are you up to?
 pub enum ProjectCommand {
-    #[strum_discriminants(strum(message = "new  -  Initializes a new project"))]
+    #[strum_discriminants(strum(message = "new           -  Initializes a new project"))]
     /// Initializes a new project
     New(self::new::New),
+    #[strum_discriminants(strum(
+        message = "setup-ci-key  -  Adds a function-call access key for deploying components from CI"
+    ))]
+    /// Adds a function-call access key for deploying components from CI
+    SetupCiKey(self::setup_ci_key::SetupCiKey),
 }
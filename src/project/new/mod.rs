@@ -34,11 +34,12 @@ impl NewContext {
             .output()
             .wrap_err("Failed to execute process: `git init`")?;
 
-        println!("New project is created at '{}'\n", project_dir.display());
-        println!("Now you can build and deploy your project:");
-        println!("1. Install dependencies: `npm install`");
-        println!("2. Edit components");
-        println!("3. Deploy: `npm run deploy`");
+        crate::human_println!("New project is created at '{}'\n", project_dir.display());
+        crate::human_println!("Now you can build and deploy your project:");
+        crate::human_println!("1. Install dependencies: `npm install`");
+        crate::human_println!("2. Edit components");
+        crate::human_println!("3. Deploy: `npm run deploy`");
+        crate::output::emit(&serde_json::json!({ "project_dir": project_dir }))?;
 
         Ok(Self)
     }
@@ -0,0 +1,299 @@
+//! The access key can only be added by a transaction to the account itself, while the write
+//! permission is granted by a call to the SocialDB contract, so the two actions cannot be batched
+//! into a single transaction. The command signs and sends both transactions with the same network
+//! and signing options (which are only entered once in the interactive mode).
+use std::sync::{Arc, Mutex};
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = near_cli_rs::GlobalContext)]
+#[interactive_clap(output_context = SetupCiKeyContext)]
+#[interactive_clap(skip_default_from_cli)]
+pub struct SetupCiKey {
+    #[interactive_clap(skip_default_input_arg)]
+    /// Which account will the CI deploy the components to?
+    account_id: near_cli_rs::types::account_id::AccountId,
+    /// Change SocialDb prefix (default: "widget")
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    social_db_folder: Option<String>,
+    /// Change the allowance of the new access key (default: "1 NEAR")
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    allowance: Option<near_cli_rs::types::near_token::NearToken>,
+    /// Change the storage deposit attached to the write permission grant (default: "1 NEAR")
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    storage_deposit: Option<near_cli_rs::types::near_token::NearToken>,
+    #[interactive_clap(named_arg)]
+    /// Select network
+    network_config: near_cli_rs::network_for_transaction::NetworkForTransactionArgs,
+}
+
+#[derive(Clone)]
+pub struct SetupCiKeyContext {
+    global_context: near_cli_rs::GlobalContext,
+    account_id: near_primitives::types::AccountId,
+    social_db_folder: String,
+    allowance: near_cli_rs::types::near_token::NearToken,
+    storage_deposit: near_cli_rs::types::near_token::NearToken,
+    secret_key: near_crypto::SecretKey,
+    /// The hash of the AddKey transaction once it is sent successfully
+    add_key_transaction_hash: Arc<Mutex<Option<near_primitives::hash::CryptoHash>>>,
+}
+
+impl SetupCiKeyContext {
+    pub fn from_previous_context(
+        previous_context: near_cli_rs::GlobalContext,
+        scope: &<SetupCiKey as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        Ok(Self {
+            global_context: previous_context,
+            account_id: scope.account_id.clone().into(),
+            social_db_folder: scope
+                .social_db_folder
+                .clone()
+                .unwrap_or("widget".to_owned()),
+            allowance: scope.allowance.unwrap_or(
+                near_cli_rs::types::near_token::NearToken::from_yoctonear(10u128.pow(24)),
+            ),
+            storage_deposit: scope.storage_deposit.unwrap_or(
+                near_cli_rs::types::near_token::NearToken::from_yoctonear(10u128.pow(24)),
+            ),
+            secret_key: near_crypto::SecretKey::from_random(near_crypto::KeyType::ED25519),
+            add_key_transaction_hash: Arc::new(Mutex::new(None)),
+        })
+    }
+
+    fn add_key_action_context(&self) -> near_cli_rs::commands::ActionContext {
+        let account_id = self.account_id.clone();
+        let public_key = self.secret_key.public_key();
+        let allowance = self.allowance;
+
+        let get_prepopulated_transaction_after_getting_network_callback: near_cli_rs::commands::GetPrepopulatedTransactionAfterGettingNetworkCallback = std::sync::Arc::new({
+            move |network_config| {
+                let near_social_account_id = &crate::common::get_near_social_account_id(network_config)?;
+
+                Ok(near_cli_rs::commands::PrepopulatedTransaction {
+                    signer_id: account_id.clone(),
+                    receiver_id: account_id.clone(),
+                    actions: vec![near_primitives::transaction::Action::AddKey(Box::new(
+                        near_primitives::transaction::AddKeyAction {
+                            public_key: public_key.clone(),
+                            access_key: near_primitives::account::AccessKey {
+                                nonce: 0,
+                                permission: near_primitives::account::AccessKeyPermission::FunctionCall(
+                                    near_primitives::account::FunctionCallPermission {
+                                        allowance: Some(allowance.as_yoctonear()),
+                                        receiver_id: near_social_account_id.to_string(),
+                                        method_names: vec!["set".to_string()],
+                                    },
+                                ),
+                            },
+                        },
+                    ))],
+                })
+            }
+        });
+
+        let on_after_sending_transaction_callback: near_cli_rs::transaction_signature_options::OnAfterSendingTransactionCallback = std::sync::Arc::new({
+            let account_id = self.account_id.clone();
+            let public_key = self.secret_key.public_key();
+            let add_key_transaction_hash = self.add_key_transaction_hash.clone();
+            move |transaction_info, _network_config| {
+                let near_primitives::views::FinalExecutionStatus::SuccessValue(_) = transaction_info.status else {
+                    color_eyre::eyre::bail!("Could not add the CI access key to <{account_id}>");
+                };
+                eprintln!("The function-call access key <{public_key}> was added to <{account_id}>\n");
+                *add_key_transaction_hash.lock().unwrap() = Some(transaction_info.transaction_outcome.id);
+                Ok(())
+            }
+        });
+
+        near_cli_rs::commands::ActionContext {
+            global_context: self.global_context.clone(),
+            interacting_with_account_ids: vec![self.account_id.clone()],
+            get_prepopulated_transaction_after_getting_network_callback,
+            on_before_signing_callback: std::sync::Arc::new(
+                |_prepolulated_unsinged_transaction, _network_config| Ok(()),
+            ),
+            on_before_sending_transaction_callback: std::sync::Arc::new(
+                |_signed_transaction, _network_config| Ok(String::new()),
+            ),
+            on_after_sending_transaction_callback,
+        }
+    }
+
+    fn grant_write_permission_action_context(&self) -> near_cli_rs::commands::ActionContext {
+        let account_id = self.account_id.clone();
+        let public_key = self.secret_key.public_key();
+        let social_db_key = format!("{}/{}", self.account_id, self.social_db_folder);
+        let storage_deposit = self.storage_deposit;
+
+        let get_prepopulated_transaction_after_getting_network_callback: near_cli_rs::commands::GetPrepopulatedTransactionAfterGettingNetworkCallback = std::sync::Arc::new({
+            let social_db_key = social_db_key.clone();
+            move |network_config| {
+                let near_social_account_id = crate::common::get_near_social_account_id(network_config)?;
+
+                Ok(near_cli_rs::commands::PrepopulatedTransaction {
+                    signer_id: account_id.clone(),
+                    receiver_id: near_social_account_id,
+                    actions: vec![near_primitives::transaction::Action::FunctionCall(Box::new(
+                        near_primitives::transaction::FunctionCallAction {
+                            method_name: "grant_write_permission".to_string(),
+                            args: serde_json::json!({
+                                "public_key": public_key.to_string(),
+                                "keys": [social_db_key],
+                            })
+                            .to_string()
+                            .into_bytes(),
+                            gas: near_cli_rs::common::NearGas::from_tgas(100).as_gas(),
+                            // The deposit covers the storage of the deployed components
+                            deposit: storage_deposit.as_yoctonear(),
+                        },
+                    ))],
+                })
+            }
+        });
+
+        let on_after_sending_transaction_callback: near_cli_rs::transaction_signature_options::OnAfterSendingTransactionCallback = std::sync::Arc::new({
+            let account_id = self.account_id.clone();
+            let secret_key = self.secret_key.clone();
+            let add_key_transaction_hash = self.add_key_transaction_hash.clone();
+            move |transaction_info, _network_config| {
+                let public_key = secret_key.public_key();
+                let near_primitives::views::FinalExecutionStatus::SuccessValue(_) = transaction_info.status else {
+                    color_eyre::eyre::bail!(
+                        "Could not grant the CI access key <{public_key}> write permission to <{social_db_key}>"
+                    );
+                };
+                eprintln!("The CI access key <{public_key}> was granted write permission to <{social_db_key}> with {storage_deposit} of storage deposit\n");
+
+                crate::human_println!("Add the following repository secret (Settings > Secrets and Variables > Actions):");
+                crate::human_println!("  SIGNER_PRIVATE_KEY: {secret_key}\n");
+                crate::human_println!("Use the following inputs for the reusable deploy workflow:");
+                crate::human_println!("  deploy-account-address: {account_id}");
+                crate::human_println!("  signer-account-address: {account_id}");
+                crate::human_println!("  signer-public-key: {public_key}");
+                crate::output::emit(&serde_json::json!({
+                    "add_key_transaction_hash": *add_key_transaction_hash.lock().unwrap(),
+                    "transaction_hash": transaction_info.transaction_outcome.id,
+                    "account_id": account_id,
+                    "public_key": public_key,
+                    "private_key": secret_key,
+                    "granted_keys": [social_db_key],
+                }))
+            }
+        });
+
+        near_cli_rs::commands::ActionContext {
+            global_context: self.global_context.clone(),
+            interacting_with_account_ids: vec![self.account_id.clone()],
+            get_prepopulated_transaction_after_getting_network_callback,
+            on_before_signing_callback: std::sync::Arc::new(
+                |_prepolulated_unsinged_transaction, _network_config| Ok(()),
+            ),
+            on_before_sending_transaction_callback: std::sync::Arc::new(
+                |_signed_transaction, _network_config| Ok(String::new()),
+            ),
+            on_after_sending_transaction_callback,
+        }
+    }
+}
+
+impl interactive_clap::FromCli for SetupCiKey {
+    type FromCliContext = near_cli_rs::GlobalContext;
+    type FromCliError = color_eyre::eyre::Error;
+
+    fn from_cli(
+        optional_clap_variant: Option<<SetupCiKey as interactive_clap::ToCli>::CliVariant>,
+        context: Self::FromCliContext,
+    ) -> interactive_clap::ResultFromCli<
+        <Self as interactive_clap::ToCli>::CliVariant,
+        Self::FromCliError,
+    >
+    where
+        Self: Sized + interactive_clap::ToCli,
+    {
+        let mut clap_variant = optional_clap_variant.unwrap_or_default();
+
+        if clap_variant.account_id.is_none() {
+            clap_variant.account_id = match Self::input_account_id(&context) {
+                Ok(Some(account_id)) => Some(account_id),
+                Ok(None) => return interactive_clap::ResultFromCli::Cancel(Some(clap_variant)),
+                Err(err) => return interactive_clap::ResultFromCli::Err(Some(clap_variant), err),
+            };
+        }
+        let new_context_scope = InteractiveClapContextScopeForSetupCiKey {
+            account_id: clap_variant.account_id.clone().expect("Unexpected error"),
+            social_db_folder: clap_variant.social_db_folder.clone(),
+            allowance: clap_variant.allowance,
+            storage_deposit: clap_variant.storage_deposit,
+        };
+        let new_context =
+            match SetupCiKeyContext::from_previous_context(context, &new_context_scope) {
+                Ok(new_context) => new_context,
+                Err(err) => return interactive_clap::ResultFromCli::Err(Some(clap_variant), err),
+            };
+
+        let optional_network_config = clap_variant.network_config.take().map(
+            |ClapNamedArgNetworkForTransactionArgsForSetupCiKey::NetworkConfig(cli_arg)| cli_arg,
+        );
+        let cli_network_config = match <near_cli_rs::network_for_transaction::NetworkForTransactionArgs as interactive_clap::FromCli>::from_cli(
+            optional_network_config,
+            new_context.add_key_action_context(),
+        ) {
+            interactive_clap::ResultFromCli::Ok(cli_network_config) => cli_network_config,
+            interactive_clap::ResultFromCli::Cancel(optional_cli_network_config) => {
+                clap_variant.network_config = optional_cli_network_config
+                    .map(ClapNamedArgNetworkForTransactionArgsForSetupCiKey::NetworkConfig);
+                return interactive_clap::ResultFromCli::Cancel(Some(clap_variant));
+            }
+            interactive_clap::ResultFromCli::Back => return interactive_clap::ResultFromCli::Back,
+            interactive_clap::ResultFromCli::Err(optional_cli_network_config, err) => {
+                clap_variant.network_config = optional_cli_network_config
+                    .map(ClapNamedArgNetworkForTransactionArgsForSetupCiKey::NetworkConfig);
+                return interactive_clap::ResultFromCli::Err(Some(clap_variant), err);
+            }
+        };
+        clap_variant.network_config = Some(
+            ClapNamedArgNetworkForTransactionArgsForSetupCiKey::NetworkConfig(
+                cli_network_config.clone(),
+            ),
+        );
+
+        // Nothing to grant when the AddKey transaction was not sent (e.g. it was only displayed)
+        if new_context
+            .add_key_transaction_hash
+            .lock()
+            .unwrap()
+            .is_none()
+        {
+            return interactive_clap::ResultFromCli::Ok(clap_variant);
+        }
+        match <near_cli_rs::network_for_transaction::NetworkForTransactionArgs as interactive_clap::FromCli>::from_cli(
+            Some(cli_network_config),
+            new_context.grant_write_permission_action_context(),
+        ) {
+            interactive_clap::ResultFromCli::Ok(_) => {
+                interactive_clap::ResultFromCli::Ok(clap_variant)
+            }
+            interactive_clap::ResultFromCli::Cancel(_) | interactive_clap::ResultFromCli::Back => {
+                interactive_clap::ResultFromCli::Cancel(Some(clap_variant))
+            }
+            interactive_clap::ResultFromCli::Err(_, err) => {
+                interactive_clap::ResultFromCli::Err(Some(clap_variant), err)
+            }
+        }
+    }
+}
+
+impl SetupCiKey {
+    fn input_account_id(
+        context: &near_cli_rs::GlobalContext,
+    ) -> color_eyre::eyre::Result<Option<near_cli_rs::types::account_id::AccountId>> {
+        near_cli_rs::common::input_signer_account_id_from_used_account_list(
+            &context.config.credentials_home_dir,
+            "Which account will the CI deploy the components to?",
+        )
+    }
+}
@@ -0,0 +1,309 @@
+//! RPC failover across an ordered list of endpoints.
+//!
+//! Every network uses its configured `rpc_url` first, followed by the fallback endpoints
+//! registered with [`set_fallback_rpc_urls`]. The endpoints of a network are health checked on
+//! its first request, and an endpoint that fails with a transport error, a server error, a timeout
+//! or the health check is marked as unhealthy for its network and skipped for the rest of the run.
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex, OnceLock};
+
+use color_eyre::eyre::WrapErr;
+
+/// The time to wait for an endpoint to respond to the `status` health check request.
+const HEALTH_CHECK_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+
+static FALLBACK_RPC_URLS: OnceLock<HashMap<String, Vec<url::Url>>> = OnceLock::new();
+
+/// The endpoint health of a network, shared by all the [`RpcEndpoints`] of the network within
+/// the process.
+#[derive(Default)]
+struct EndpointHealth {
+    /// The server addresses of the endpoints that have failed.
+    unhealthy: Mutex<HashSet<String>>,
+    /// Initialized once the endpoints have been health checked.
+    health_check: tokio::sync::OnceCell<()>,
+}
+
+/// The endpoint health per network name.
+static ENDPOINT_HEALTH: OnceLock<Mutex<HashMap<String, Arc<EndpointHealth>>>> = OnceLock::new();
+
+/// Registers the fallback RPC endpoints per network name (in the order they should be tried).
+///
+/// Only the first call has an effect.
+pub fn set_fallback_rpc_urls(fallback_rpc_urls: HashMap<String, Vec<url::Url>>) {
+    let _ = FALLBACK_RPC_URLS.set(fallback_rpc_urls);
+}
+
+/// Returns the fallback RPC endpoints registered for the network.
+pub fn fallback_rpc_urls(network_name: &str) -> &'static [url::Url] {
+    FALLBACK_RPC_URLS
+        .get()
+        .and_then(|fallback_rpc_urls| fallback_rpc_urls.get(network_name))
+        .map_or(&[], Vec::as_slice)
+}
+
+/// Returns the endpoint health of the network, shared within the process.
+fn endpoint_health(network_name: &str) -> Arc<EndpointHealth> {
+    ENDPOINT_HEALTH
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap()
+        .entry(network_name.to_owned())
+        .or_default()
+        .clone()
+}
+
+/// The error returned when an RPC endpoint does not respond in time.
+#[derive(Debug)]
+pub struct RpcTimeoutError {
+    pub rpc_url: String,
+    pub timeout: std::time::Duration,
+}
+
+impl std::fmt::Display for RpcTimeoutError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "RPC endpoint <{}> did not respond within {:?}",
+            self.rpc_url, self.timeout
+        )
+    }
+}
+
+impl std::error::Error for RpcTimeoutError {}
+
+/// An ordered list of RPC endpoints that are tried one after another.
+pub struct RpcEndpoints {
+    network_name: Option<String>,
+    json_rpc_clients: Vec<near_jsonrpc_client::JsonRpcClient>,
+    health: Arc<EndpointHealth>,
+    timeout: std::time::Duration,
+}
+
+impl RpcEndpoints {
+    /// The default time to wait for a single RPC endpoint to respond.
+    pub const DEFAULT_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(30);
+
+    pub fn new(json_rpc_clients: Vec<near_jsonrpc_client::JsonRpcClient>) -> Self {
+        Self {
+            network_name: None,
+            json_rpc_clients,
+            health: Default::default(),
+            timeout: Self::DEFAULT_TIMEOUT,
+        }
+    }
+
+    /// The configured `rpc_url` of the network followed by its fallback endpoints.
+    ///
+    /// The `rpc_api_key` is only sent to the configured `rpc_url`. The endpoint health is shared
+    /// with all the other endpoint lists of the network, and the endpoints are only health checked
+    /// once a request is sent to the network.
+    pub fn for_network(network_config: &near_cli_rs::config::NetworkConfig) -> Self {
+        let fallback_json_rpc_clients = fallback_rpc_urls(&network_config.network_name)
+            .iter()
+            .filter(|rpc_url| **rpc_url != network_config.rpc_url)
+            .map(|rpc_url| {
+                near_cli_rs::config::NetworkConfig {
+                    rpc_url: rpc_url.clone(),
+                    rpc_api_key: None,
+                    ..network_config.clone()
+                }
+                .json_rpc_client()
+            });
+        Self {
+            network_name: Some(network_config.network_name.clone()),
+            health: endpoint_health(&network_config.network_name),
+            ..Self::new(
+                std::iter::once(network_config.json_rpc_client())
+                    .chain(fallback_json_rpc_clients)
+                    .collect(),
+            )
+        }
+    }
+
+    /// The network name, or the primary endpoint URL if the endpoints are not bound to a network.
+    pub fn network(&self) -> &str {
+        self.network_name
+            .as_deref()
+            .unwrap_or_else(|| self.json_rpc_clients[0].server_addr())
+    }
+
+    pub fn with_timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Sends the request to the first healthy endpoint, switching to the next one on transport
+    /// errors, server errors and timeouts.
+    ///
+    /// Handler errors (e.g. an unknown account) are returned right away since every endpoint
+    /// would return the same.
+    pub async fn call<M>(&self, method: &M) -> color_eyre::eyre::Result<M::Response>
+    where
+        M: near_jsonrpc_client::methods::RpcMethod,
+        near_jsonrpc_client::errors::JsonRpcError<M::Error>:
+            std::error::Error + Send + Sync + 'static,
+    {
+        self.check_health().await;
+        let mut last_err = None;
+        for index in self.candidate_indices() {
+            let json_rpc_client = &self.json_rpc_clients[index];
+            if index > 0 {
+                tracing::info!(
+                    target: "near_teach_me",
+                    parent: &tracing::Span::none(),
+                    "Switching to the fallback RPC endpoint <{}>",
+                    json_rpc_client.server_addr()
+                );
+            }
+            let err = match tokio::time::timeout(self.timeout, json_rpc_client.call(method)).await {
+                Ok(Ok(response)) => return Ok(response),
+                Ok(Err(err)) if !is_endpoint_failure(&err) => return Err(err.into()),
+                Ok(Err(err)) => color_eyre::eyre::Report::new(err),
+                Err(_) => color_eyre::eyre::Report::new(RpcTimeoutError {
+                    rpc_url: json_rpc_client.server_addr().to_owned(),
+                    timeout: self.timeout,
+                }),
+            };
+            tracing::info!(
+                target: "near_teach_me",
+                parent: &tracing::Span::none(),
+                "RPC endpoint <{}> is unhealthy, it will be skipped:\n{}",
+                json_rpc_client.server_addr(),
+                near_cli_rs::common::indent_payload(&format!("{err:#}"))
+            );
+            self.mark_unhealthy(json_rpc_client);
+            last_err = Some(err);
+        }
+        Err(last_err
+            .unwrap_or_else(|| color_eyre::eyre::eyre!("There are no RPC endpoints to call")))
+    }
+
+    /// Selects the first endpoint of the network that responds to a `status` request, once per
+    /// network and only when it has fallback endpoints.
+    async fn check_health(&self) {
+        if self.network_name.is_none() || self.json_rpc_clients.len() == 1 {
+            return;
+        }
+        self.health
+            .health_check
+            .get_or_init(|| async {
+                for json_rpc_client in &self.json_rpc_clients {
+                    let result = tokio::time::timeout(
+                        HEALTH_CHECK_TIMEOUT,
+                        json_rpc_client
+                            .call(near_jsonrpc_client::methods::status::RpcStatusRequest),
+                    )
+                    .await;
+                    if matches!(result, Ok(Ok(_))) {
+                        tracing::info!(
+                            target: "near_teach_me",
+                            parent: &tracing::Span::none(),
+                            "Selected RPC endpoint <{}> for network <{}>",
+                            json_rpc_client.server_addr(),
+                            self.network()
+                        );
+                        return;
+                    }
+                    tracing::info!(
+                        target: "near_teach_me",
+                        parent: &tracing::Span::none(),
+                        "RPC endpoint <{}> did not pass the health check",
+                        json_rpc_client.server_addr()
+                    );
+                    self.mark_unhealthy(json_rpc_client);
+                }
+                tracing::info!(
+                    target: "near_teach_me",
+                    parent: &tracing::Span::none(),
+                    "None of the RPC endpoints of network <{}> responded",
+                    self.network()
+                );
+            })
+            .await;
+    }
+
+    fn mark_unhealthy(&self, json_rpc_client: &near_jsonrpc_client::JsonRpcClient) {
+        self.health
+            .unhealthy
+            .lock()
+            .unwrap()
+            .insert(json_rpc_client.server_addr().to_owned());
+    }
+
+    /// Returns the client for the first endpoint that has not failed yet.
+    pub fn json_rpc_client(&self) -> &near_jsonrpc_client::JsonRpcClient {
+        &self.json_rpc_clients[self.candidate_indices()[0]]
+    }
+
+    /// The healthy endpoints, or all of them once every endpoint has failed (so the callers
+    /// retrying a request are not left without endpoints).
+    fn candidate_indices(&self) -> Vec<usize> {
+        let unhealthy = self.health.unhealthy.lock().unwrap();
+        let healthy = (0..self.json_rpc_clients.len())
+            .filter(|index| !unhealthy.contains(self.json_rpc_clients[*index].server_addr()))
+            .collect::<Vec<_>>();
+        if healthy.is_empty() {
+            (0..self.json_rpc_clients.len()).collect()
+        } else {
+            healthy
+        }
+    }
+}
+
+/// Errors that say nothing about the request itself, so another endpoint may succeed.
+pub fn is_endpoint_failure<E>(err: &near_jsonrpc_client::errors::JsonRpcError<E>) -> bool {
+    use near_jsonrpc_client::errors::{
+        JsonRpcError, JsonRpcServerError, JsonRpcServerResponseStatusError,
+    };
+
+    matches!(
+        err,
+        JsonRpcError::TransportError(_)
+            | JsonRpcError::ServerError(
+                JsonRpcServerError::InternalError { .. }
+                    | JsonRpcServerError::NonContextualError(_)
+                    | JsonRpcServerError::ResponseStatusError(
+                        JsonRpcServerResponseStatusError::TooManyRequests
+                            | JsonRpcServerResponseStatusError::TimeoutError
+                            | JsonRpcServerResponseStatusError::ServiceUnavailable
+                            | JsonRpcServerResponseStatusError::Unexpected { .. }
+                    )
+            )
+    )
+}
+
+/// Health checks the endpoints of the network connection and makes the selected one its
+/// `rpc_url`, so near CLI also sends the transactions through it.
+pub fn select_healthy_rpc_url(network_config: &mut near_cli_rs::config::NetworkConfig) {
+    let rpc_endpoints = RpcEndpoints::for_network(network_config);
+    crate::common::block_on(rpc_endpoints.check_health());
+    let rpc_url = rpc_endpoints.json_rpc_client().server_addr();
+    if rpc_url != network_config.json_rpc_client().server_addr() {
+        if let Ok(rpc_url) = url::Url::parse(rpc_url) {
+            network_config.rpc_api_key = None;
+            network_config.rpc_url = rpc_url;
+        }
+    }
+}
+
+/// Parses `<network-name>=<rpc-url>` entries into the fallback endpoints per network.
+pub fn parse_fallback_rpc_urls(
+    entries: &[String],
+) -> color_eyre::eyre::Result<HashMap<String, Vec<url::Url>>> {
+    let mut fallback_rpc_urls: HashMap<String, Vec<url::Url>> = HashMap::new();
+    for entry in entries {
+        let (network_name, rpc_url) = entry.split_once('=').ok_or_else(|| {
+            color_eyre::eyre::eyre!(
+                "Invalid fallback RPC endpoint <{entry}>, expected <network-name>=<rpc-url>"
+            )
+        })?;
+        let rpc_url = url::Url::parse(rpc_url.trim())
+            .wrap_err_with(|| format!("Invalid fallback RPC endpoint URL <{rpc_url}>"))?;
+        fallback_rpc_urls
+            .entry(network_name.trim().to_owned())
+            .or_default()
+            .push(rpc_url);
+    }
+    Ok(fallback_rpc_urls)
+}
@@ -0,0 +1,68 @@
+use color_eyre::eyre::WrapErr;
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = near_cli_rs::GlobalContext)]
+#[interactive_clap(output_context = AddNetworkContext)]
+pub struct AddNetwork {
+    /// The name of the network in the near CLI config (default: "sandbox")
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    network_name: Option<String>,
+    /// The RPC URL of the sandbox node (default: "http://127.0.0.1:3030/")
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    rpc_url: Option<near_cli_rs::types::url::Url>,
+    /// The account the SocialDB contract is deployed to (default: "social.test.near")
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    social_db_contract: Option<near_cli_rs::types::account_id::AccountId>,
+}
+
+#[derive(Debug, Clone)]
+pub struct AddNetworkContext;
+
+impl AddNetworkContext {
+    pub fn from_previous_context(
+        previous_context: near_cli_rs::GlobalContext,
+        scope: &<AddNetwork as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let network_name = scope.network_name.clone().unwrap_or("sandbox".to_owned());
+        let rpc_url = match &scope.rpc_url {
+            Some(rpc_url) => rpc_url.0.clone(),
+            None => {
+                "http://127.0.0.1:3030/"
+                    .parse::<near_cli_rs::types::url::Url>()?
+                    .0
+            }
+        };
+        let near_social_db_contract_account_id = match &scope.social_db_contract {
+            Some(account_id) => account_id.clone().into(),
+            None => "social.test.near".parse()?,
+        };
+
+        let mut config = previous_context.config;
+        config.network_connection.insert(
+            network_name.clone(),
+            near_cli_rs::config::NetworkConfig {
+                network_name: network_name.clone(),
+                rpc_url: rpc_url.clone(),
+                rpc_api_key: None,
+                wallet_url: rpc_url.clone(),
+                explorer_transaction_url: rpc_url.clone(),
+                linkdrop_account_id: None,
+                near_social_db_contract_account_id: Some(near_social_db_contract_account_id),
+                faucet_url: None,
+                meta_transaction_relayer_url: None,
+                fastnear_url: None,
+                staking_pools_factory_account_id: None,
+                coingecko_url: None,
+            },
+        );
+        config
+            .write_config_toml()
+            .wrap_err("Failed to update the near CLI config")?;
+
+        eprintln!("The <{network_name}> network ({rpc_url}) is added to the near CLI config");
+        Ok(Self)
+    }
+}
@@ -0,0 +1,136 @@
+use color_eyre::eyre::WrapErr;
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = near_cli_rs::GlobalContext)]
+#[interactive_clap(output_context = DeploySocialDbContext)]
+pub struct DeploySocialDb {
+    /// Which account should the SocialDB contract be deployed to (e.g. social.test.near)?
+    contract_account_id: near_cli_rs::types::account_id::AccountId,
+    /// What is the path to the SocialDB contract wasm file?
+    #[interactive_clap(long)]
+    wasm_file: near_cli_rs::types::path_buf::PathBuf,
+    /// The initial balance of the contract account (default: "50 NEAR")
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    initial_balance: Option<near_cli_rs::types::near_token::NearToken>,
+    #[interactive_clap(named_arg)]
+    /// Select network
+    network_config: near_cli_rs::network_for_transaction::NetworkForTransactionArgs,
+}
+
+#[derive(Clone)]
+pub struct DeploySocialDbContext {
+    global_context: near_cli_rs::GlobalContext,
+    contract_account_id: near_primitives::types::AccountId,
+    signer_account_id: near_primitives::types::AccountId,
+    code: Vec<u8>,
+    initial_balance: near_cli_rs::types::near_token::NearToken,
+}
+
+impl DeploySocialDbContext {
+    pub fn from_previous_context(
+        previous_context: near_cli_rs::GlobalContext,
+        scope: &<DeploySocialDb as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let contract_account_id: near_primitives::types::AccountId =
+            scope.contract_account_id.clone().into();
+        let wasm_file: &std::path::Path = scope.wasm_file.as_ref();
+        let code = std::fs::read(wasm_file)
+            .wrap_err_with(|| format!("Failed to read the wasm file: {}", wasm_file.display()))?;
+        Ok(Self {
+            global_context: previous_context,
+            signer_account_id: super::parent_account_id(&contract_account_id)?,
+            contract_account_id,
+            code,
+            initial_balance: scope.initial_balance.unwrap_or(
+                near_cli_rs::types::near_token::NearToken::from_yoctonear(50 * 10u128.pow(24)),
+            ),
+        })
+    }
+}
+
+impl From<DeploySocialDbContext> for near_cli_rs::commands::ActionContext {
+    fn from(item: DeploySocialDbContext) -> Self {
+        let signer_id = item.signer_account_id.clone();
+        let contract_account_id = item.contract_account_id.clone();
+        let code = item.code.clone();
+        let initial_balance = item.initial_balance;
+
+        // The contract owner is the predecessor of `new`, so the parent account initializes
+        // the contract and switches it to the `Live` status within the same transaction.
+        let get_prepopulated_transaction_after_getting_network_callback: near_cli_rs::commands::GetPrepopulatedTransactionAfterGettingNetworkCallback = std::sync::Arc::new({
+            move |_network_config| {
+                Ok(near_cli_rs::commands::PrepopulatedTransaction {
+                    signer_id: signer_id.clone(),
+                    receiver_id: contract_account_id.clone(),
+                    actions: vec![
+                        near_primitives::transaction::Action::CreateAccount(
+                            near_primitives::transaction::CreateAccountAction {},
+                        ),
+                        near_primitives::transaction::Action::Transfer(
+                            near_primitives::transaction::TransferAction {
+                                deposit: initial_balance.as_yoctonear(),
+                            },
+                        ),
+                        near_primitives::transaction::Action::DeployContract(
+                            near_primitives::transaction::DeployContractAction {
+                                code: code.clone(),
+                            },
+                        ),
+                        near_primitives::transaction::Action::FunctionCall(Box::new(
+                            near_primitives::transaction::FunctionCallAction {
+                                method_name: "new".to_string(),
+                                args: serde_json::json!({}).to_string().into_bytes(),
+                                gas: near_cli_rs::common::NearGas::from_tgas(100).as_gas(),
+                                deposit: 0,
+                            },
+                        )),
+                        near_primitives::transaction::Action::FunctionCall(Box::new(
+                            near_primitives::transaction::FunctionCallAction {
+                                method_name: "set_status".to_string(),
+                                args: serde_json::json!({ "status": "Live" })
+                                    .to_string()
+                                    .into_bytes(),
+                                gas: near_cli_rs::common::NearGas::from_tgas(100).as_gas(),
+                                deposit: 0,
+                            },
+                        )),
+                    ],
+                })
+            }
+        });
+
+        let on_after_sending_transaction_callback: near_cli_rs::transaction_signature_options::OnAfterSendingTransactionCallback = std::sync::Arc::new({
+            let contract_account_id = item.contract_account_id.clone();
+            move |transaction_info, network_config| {
+                let near_primitives::views::FinalExecutionStatus::SuccessValue(_) = transaction_info.status else {
+                    color_eyre::eyre::bail!("Could not deploy the SocialDB contract to <{contract_account_id}>");
+                };
+                eprintln!(
+                    "The SocialDB contract is deployed to <{contract_account_id}> on the <{}> network",
+                    network_config.network_name
+                );
+                if network_config.near_social_db_contract_account_id.as_ref() != Some(&contract_account_id) {
+                    eprintln!(
+                        "Note: the <{}> network is configured with a different SocialDB contract, use `--social-db-contract {contract_account_id}` or `bos sandbox add-network` to point bos to it",
+                        network_config.network_name
+                    );
+                }
+                Ok(())
+            }
+        });
+
+        Self {
+            global_context: item.global_context,
+            interacting_with_account_ids: vec![item.signer_account_id, item.contract_account_id],
+            get_prepopulated_transaction_after_getting_network_callback,
+            on_before_signing_callback: std::sync::Arc::new(
+                |_prepolulated_unsinged_transaction, _network_config| Ok(()),
+            ),
+            on_before_sending_transaction_callback: std::sync::Arc::new(
+                |_signed_transaction, _network_config| Ok(String::new()),
+            ),
+            on_after_sending_transaction_callback,
+        }
+    }
+}
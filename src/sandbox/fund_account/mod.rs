@@ -0,0 +1,123 @@
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = near_cli_rs::GlobalContext)]
+#[interactive_clap(output_context = FundAccountContext)]
+pub struct FundAccount {
+    /// What is the new test account ID (e.g. alice.test.near)?
+    new_account_id: near_cli_rs::types::account_id::AccountId,
+    /// The full access key of the new account (a new keypair is generated by default)
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    public_key: Option<near_cli_rs::types::public_key::PublicKey>,
+    /// The initial balance of the new account (default: "100 NEAR")
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    initial_balance: Option<near_cli_rs::types::near_token::NearToken>,
+    #[interactive_clap(named_arg)]
+    /// Select network
+    network_config: near_cli_rs::network_for_transaction::NetworkForTransactionArgs,
+}
+
+#[derive(Clone)]
+pub struct FundAccountContext {
+    global_context: near_cli_rs::GlobalContext,
+    new_account_id: near_primitives::types::AccountId,
+    signer_account_id: near_primitives::types::AccountId,
+    public_key: near_crypto::PublicKey,
+    secret_key: Option<near_crypto::SecretKey>,
+    initial_balance: near_cli_rs::types::near_token::NearToken,
+}
+
+impl FundAccountContext {
+    pub fn from_previous_context(
+        previous_context: near_cli_rs::GlobalContext,
+        scope: &<FundAccount as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let new_account_id: near_primitives::types::AccountId = scope.new_account_id.clone().into();
+        let (public_key, secret_key) = match &scope.public_key {
+            Some(public_key) => (public_key.0.clone(), None),
+            None => {
+                let secret_key = near_crypto::SecretKey::from_random(near_crypto::KeyType::ED25519);
+                (secret_key.public_key(), Some(secret_key))
+            }
+        };
+        Ok(Self {
+            global_context: previous_context,
+            signer_account_id: super::parent_account_id(&new_account_id)?,
+            new_account_id,
+            public_key,
+            secret_key,
+            initial_balance: scope.initial_balance.unwrap_or(
+                near_cli_rs::types::near_token::NearToken::from_yoctonear(100 * 10u128.pow(24)),
+            ),
+        })
+    }
+}
+
+impl From<FundAccountContext> for near_cli_rs::commands::ActionContext {
+    fn from(item: FundAccountContext) -> Self {
+        let signer_id = item.signer_account_id.clone();
+        let new_account_id = item.new_account_id.clone();
+        let public_key = item.public_key.clone();
+        let initial_balance = item.initial_balance;
+
+        let get_prepopulated_transaction_after_getting_network_callback: near_cli_rs::commands::GetPrepopulatedTransactionAfterGettingNetworkCallback = std::sync::Arc::new({
+            move |_network_config| {
+                Ok(near_cli_rs::commands::PrepopulatedTransaction {
+                    signer_id: signer_id.clone(),
+                    receiver_id: new_account_id.clone(),
+                    actions: vec![
+                        near_primitives::transaction::Action::CreateAccount(
+                            near_primitives::transaction::CreateAccountAction {},
+                        ),
+                        near_primitives::transaction::Action::Transfer(
+                            near_primitives::transaction::TransferAction {
+                                deposit: initial_balance.as_yoctonear(),
+                            },
+                        ),
+                        near_primitives::transaction::Action::AddKey(Box::new(
+                            near_primitives::transaction::AddKeyAction {
+                                public_key: public_key.clone(),
+                                access_key: near_primitives::account::AccessKey::full_access(),
+                            },
+                        )),
+                    ],
+                })
+            }
+        });
+
+        let on_after_sending_transaction_callback: near_cli_rs::transaction_signature_options::OnAfterSendingTransactionCallback = std::sync::Arc::new({
+            let new_account_id = item.new_account_id.clone();
+            let public_key = item.public_key.clone();
+            let secret_key = item.secret_key.clone();
+            move |transaction_info, _network_config| {
+                let near_primitives::views::FinalExecutionStatus::SuccessValue(_) = transaction_info.status else {
+                    color_eyre::eyre::bail!("Could not create the test account <{new_account_id}>");
+                };
+                eprintln!("The test account <{new_account_id}> is created with {initial_balance}");
+                crate::human_println!("Public key: {public_key}");
+                if let Some(secret_key) = &secret_key {
+                    crate::human_println!("Private key: {secret_key}");
+                }
+                crate::output::emit(&serde_json::json!({
+                    "transaction_hash": transaction_info.transaction_outcome.id,
+                    "account_id": new_account_id,
+                    "public_key": public_key,
+                    "private_key": secret_key,
+                }))
+            }
+        });
+
+        Self {
+            global_context: item.global_context,
+            interacting_with_account_ids: vec![item.signer_account_id, item.new_account_id],
+            get_prepopulated_transaction_after_getting_network_callback,
+            on_before_signing_callback: std::sync::Arc::new(
+                |_prepolulated_unsinged_transaction, _network_config| Ok(()),
+            ),
+            on_before_sending_transaction_callback: std::sync::Arc::new(
+                |_signed_transaction, _network_config| Ok(String::new()),
+            ),
+            on_after_sending_transaction_callback,
+        }
+    }
+}
@@ -0,0 +1,50 @@
+use strum::{EnumDiscriminants, EnumIter, EnumMessage};
+
+mod add_network;
+mod deploy_social_db;
+mod fund_account;
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(context = near_cli_rs::GlobalContext)]
+pub struct Sandbox {
+    #[interactive_clap(subcommand)]
+    command: SandboxCommand,
+}
+
+#[derive(Debug, EnumDiscriminants, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(context = near_cli_rs::GlobalContext)]
+#[strum_discriminants(derive(EnumMessage, EnumIter))]
+/// What are you up to?
+pub enum SandboxCommand {
+    #[strum_discriminants(strum(
+        message = "add-network        -  Registers a locally running sandbox node in the near CLI config"
+    ))]
+    /// Registers a locally running sandbox node in the near CLI config
+    AddNetwork(self::add_network::AddNetwork),
+    #[strum_discriminants(strum(
+        message = "deploy-social-db   -  Creates an account and deploys the SocialDB contract to it"
+    ))]
+    /// Creates an account and deploys the SocialDB contract to it
+    DeploySocialDb(self::deploy_social_db::DeploySocialDb),
+    #[strum_discriminants(strum(
+        message = "fund-account       -  Creates a pre-funded test account"
+    ))]
+    /// Creates a pre-funded test account
+    FundAccount(self::fund_account::FundAccount),
+}
+
+/// The sub-accounts can only be created by their parent account, so the parent account signs
+/// the sandbox transactions (e.g. `test.near` for `social.test.near`).
+fn parent_account_id(
+    account_id: &near_primitives::types::AccountId,
+) -> color_eyre::eyre::Result<near_primitives::types::AccountId> {
+    account_id
+        .as_str()
+        .split_once('.')
+        .and_then(|(_, parent_account_id)| parent_account_id.parse().ok())
+        .ok_or_else(|| {
+            color_eyre::eyre::eyre!(
+                "<{account_id}> is not a sub-account, so it cannot be created by the sandbox root account"
+            )
+        })
+}
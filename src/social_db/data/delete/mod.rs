@@ -1,7 +1,7 @@
 use std::str::FromStr;
 
 use color_eyre::eyre::{ContextCompat, WrapErr};
-use near_cli_rs::common::{CallResultExt, JsonRpcClientExt};
+use near_cli_rs::common::CallResultExt;
 
 mod sign_as;
 
@@ -9,8 +9,11 @@ mod sign_as;
 #[interactive_clap(input_context = near_cli_rs::GlobalContext)]
 #[interactive_clap(output_context = DeleteContext)]
 pub struct Delete {
-    /// Enter SocialDB key path to delete data (e.g. root.near/profile/image):
-    key: String,
+    /// Enter a comma-separated list of SocialDB key patterns to delete data (e.g. root.near/profile/image/**,root.near/post/**):
+    keys: near_cli_rs::types::vec_string::VecString,
+    #[interactive_clap(long)]
+    /// Only show the keys that would be deleted without signing the transaction
+    dry_run: bool,
     #[interactive_clap(named_arg)]
     /// Specify signer account ID
     sign_as: self::sign_as::Signer,
@@ -24,47 +27,88 @@ impl DeleteContext {
         previous_context: near_cli_rs::GlobalContext,
         scope: &<Delete as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
     ) -> color_eyre::eyre::Result<Self> {
-        let account_id = near_cli_rs::types::account_id::AccountId::from_str(
-            scope
-                .key
-                .split_once('/')
-                .wrap_err("Failed to parse account_id from this key")?
-                .0
-                .trim(),
-        )?;
+        let keys: Vec<String> = scope.keys.clone().into();
+        let account_ids = keys
+            .iter()
+            .map(|key| {
+                Ok(near_primitives::types::AccountId::from_str(
+                    key.split_once('/')
+                        .wrap_err_with(|| {
+                            format!("Failed to parse account_id from the key <{key}>")
+                        })?
+                        .0
+                        .trim(),
+                )?)
+            })
+            .collect::<color_eyre::eyre::Result<std::collections::BTreeSet<_>>>()?;
+        let account_id = match account_ids.len() {
+            0 => color_eyre::eyre::bail!("There are no keys to delete"),
+            1 => near_cli_rs::types::account_id::AccountId::from(
+                account_ids.into_iter().next().wrap_err("Internal error")?,
+            ),
+            _ => color_eyre::eyre::bail!("All the keys to delete must belong to the same account"),
+        };
+        let dry_run = scope.dry_run;
 
         let get_prepopulated_transaction_after_getting_network_callback: near_cli_rs::commands::GetPrepopulatedTransactionAfterGettingNetworkCallback = std::sync::Arc::new({
             let signer_id = account_id.clone();
-            let key = scope.key.clone();
 
             move |network_config| {
-                let near_social_account_id = crate::consts::NEAR_SOCIAL_ACCOUNT_ID.get(network_config.network_name.as_str())
-                    .wrap_err_with(|| format!("The <{}> network does not have a near-social contract.", network_config.network_name))?;
+                let near_social_account_id = &crate::common::get_near_social_account_id(network_config)?;
 
                 let input_args = serde_json::to_string(&crate::socialdb_types::SocialDbQuery {
-                    keys: vec![format!("{key}")],
+                    keys: keys.clone(),
+                    options: None,
                 })
                 .wrap_err("Internal error: could not serialize SocialDB input args")?;
 
-                let mut social_db_data_to_remove: serde_json::Value = network_config
-                    .json_rpc_client()
-                    .blocking_call_view_function(
+                let mut social_db_data_to_remove: serde_json::Value = crate::common::call_view_function_with_failover(
+                        network_config,
                         near_social_account_id,
                         "get",
                         input_args.into_bytes(),
                         near_primitives::types::Finality::Final.into(),
+                        crate::cache::CachePolicy::Bypass,
                     )
                     .wrap_err("Failed to fetch the components from SocialDB")?
                     .parse_result_from_json()
                     .wrap_err("SocialDB `get` data response cannot be parsed")?;
                 if social_db_data_to_remove.as_object().map(|result| result.is_empty()).unwrap_or(true) {
-                    println!("No keys to remove. Goodbye.");
+                    crate::human_println!("No keys to remove. Goodbye.");
+                    return Ok(near_cli_rs::commands::PrepopulatedTransaction {
+                        signer_id: signer_id.clone().into(),
+                        receiver_id: near_social_account_id.clone(),
+                        actions: vec![],
+                    });
+                }
+
+                let leaves_count = crate::common::social_db_data_leaves(&social_db_data_to_remove).len();
+                let released_storage_bytes = crate::common::estimate_released_storage_bytes(&social_db_data_to_remove);
+                crate::human_println!("\nThe following keys will be deleted:\n");
+                crate::human_print!("{}", crate::common::social_db_data_tree(&social_db_data_to_remove, false));
+                crate::human_println!(
+                    "\n{leaves_count} key(s) will be deleted, releasing an estimated {released_storage_bytes} bytes of storage (~{} that can be withdrawn from the prepaid storage)",
+                    near_cli_rs::types::near_token::NearToken::from_yoctonear(
+                        released_storage_bytes as u128 * crate::consts::STORAGE_COST_PER_BYTE
+                    )
+                );
+                if dry_run {
+                    crate::human_println!("\nDry run: nothing was deleted.");
+                    crate::output::emit(&serde_json::json!({
+                        "dry_run": true,
+                        "keys": crate::common::social_db_data_leaves(&social_db_data_to_remove)
+                            .into_iter()
+                            .map(|(path, _)| path)
+                            .collect::<Vec<_>>(),
+                        "released_storage_bytes": released_storage_bytes,
+                    }))?;
                     return Ok(near_cli_rs::commands::PrepopulatedTransaction {
                         signer_id: signer_id.clone().into(),
                         receiver_id: near_social_account_id.clone(),
                         actions: vec![],
                     });
                 }
+
                 crate::common::mark_leaf_values_as_null(&mut social_db_data_to_remove);
                 Ok(near_cli_rs::commands::PrepopulatedTransaction {
                     signer_id: signer_id.clone().into(),
@@ -87,12 +131,16 @@ impl DeleteContext {
             let account_id = account_id.clone();
 
             move |transaction_info, _network_config| {
+                crate::cache::invalidate_final_block_entries();
                 if let near_primitives::views::FinalExecutionStatus::SuccessValue(_) = transaction_info.status {
-                    println!("Keys successfully removed from <{account_id}>");
+                    crate::human_println!("Keys successfully removed from <{account_id}>");
                 } else {
                     color_eyre::eyre::bail!("Keys were not successfully removed from <{account_id}>");
                 };
-                Ok(())
+                crate::output::emit(&serde_json::json!({
+                    "transaction_hash": transaction_info.transaction_outcome.id,
+                    "account_id": account_id.to_string(),
+                }))
             }
         });
 
@@ -0,0 +1,97 @@
+use color_eyre::eyre::WrapErr;
+use near_cli_rs::common::CallResultExt;
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = near_cli_rs::GlobalContext)]
+#[interactive_clap(output_context = KeysContext)]
+pub struct Keys {
+    /// Enter SocialDB key pattern to view the keys (e.g. root.near/widget/*):
+    key: String,
+    /// What should be returned for every key: True, BlockHeight or NodeId (default: True)
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    return_type: Option<crate::socialdb_types::SocialDbKeysReturnType>,
+    #[interactive_clap(long)]
+    /// Only return the keys that have values (skip the intermediate nodes)
+    values_only: bool,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// Output format: tree (default), json, compact-json, yaml, flat (`key/path = value` per line) or raw (a single value, unquoted)
+    format: Option<super::view::ViewOutputFormat>,
+    #[interactive_clap(named_arg)]
+    /// Select network
+    network_config: near_cli_rs::network_view_at_block::NetworkViewAtBlockArgs,
+}
+
+#[derive(Clone)]
+pub struct KeysContext(near_cli_rs::network_view_at_block::ArgsForViewContext);
+
+impl KeysContext {
+    pub fn from_previous_context(
+        previous_context: near_cli_rs::GlobalContext,
+        scope: &<Keys as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let on_after_getting_block_reference_callback: near_cli_rs::network_view_at_block::OnAfterGettingBlockReferenceCallback =
+            std::sync::Arc::new({
+                let key = scope.key.clone();
+                let return_type = scope.return_type;
+                let values_only = scope.values_only;
+                let format = scope.format.unwrap_or(super::view::ViewOutputFormat::Tree);
+
+                move |network_config, block_reference| {
+                    let near_social_account_id = &crate::common::get_near_social_account_id(network_config)?;
+
+                    let input_args = serde_json::to_string(&crate::socialdb_types::SocialDbKeysQuery {
+                        keys: vec![key.clone()],
+                        options: Some(crate::socialdb_types::SocialDbKeysOptions {
+                            return_type,
+                            values_only: values_only.then_some(true),
+                        }),
+                    })
+                    .wrap_err("Internal error: could not serialize SocialDB input args")?;
+
+                    let keys: serde_json::Value = crate::common::call_view_function_with_failover(
+                            network_config,
+                            near_social_account_id,
+                            "keys",
+                            input_args.into_bytes(),
+                            block_reference.clone(),
+                            crate::cache::CachePolicy::Use,
+                        )
+                        .wrap_err_with(|| format!("Failed to fetch query for view method: 'keys {key}'"))?
+                        .parse_result_from_json()
+                        .wrap_err("SocialDB `keys` response cannot be parsed")?;
+
+                    if keys.as_object().map(|keys| keys.is_empty()).unwrap_or(true) {
+                        eprintln!("There are no keys matching <{key}>");
+                        crate::output::emit(&keys)?;
+                    } else if crate::output::is_json() {
+                        crate::output::emit(&keys)?;
+                    } else if format == super::view::ViewOutputFormat::Tree {
+                        let with_values = !matches!(
+                            return_type.unwrap_or(crate::socialdb_types::SocialDbKeysReturnType::True),
+                            crate::socialdb_types::SocialDbKeysReturnType::True
+                        );
+                        print!("{}", crate::common::social_db_data_tree(&keys, with_values));
+                    } else {
+                        super::view::print_social_db_data(&keys, format)?;
+                    }
+                    Ok(())
+                }
+            });
+
+        Ok(Self(
+            near_cli_rs::network_view_at_block::ArgsForViewContext {
+                config: previous_context.config,
+                interacting_with_account_ids: vec![],
+                on_after_getting_block_reference_callback,
+            },
+        ))
+    }
+}
+
+impl From<KeysContext> for near_cli_rs::network_view_at_block::ArgsForViewContext {
+    fn from(item: KeysContext) -> Self {
+        item.0
+    }
+}
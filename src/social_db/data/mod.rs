@@ -1,7 +1,8 @@
 use strum::{EnumDiscriminants, EnumIter, EnumMessage};
 
 mod delete;
-mod set;
+mod keys;
+pub mod set;
 mod view;
 
 #[derive(Debug, Clone, interactive_clap::InteractiveClap)]
@@ -19,6 +20,11 @@ pub enum DataCommand {
     #[strum_discriminants(strum(message = "view     -   Viewing information by a given key"))]
     /// Viewing information by a given key
     View(self::view::View),
+    #[strum_discriminants(strum(
+        message = "keys     -   Viewing the keys by a given key pattern"
+    ))]
+    /// Viewing the keys by a given key pattern
+    Keys(self::keys::Keys),
     #[strum_discriminants(strum(
         message = "set      -   Adding or updating information by a given key"
     ))]
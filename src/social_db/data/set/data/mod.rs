@@ -35,5 +35,19 @@ pub struct DataContext {
     pub global_context: near_cli_rs::GlobalContext,
     pub set_to_account_id: near_cli_rs::types::account_id::AccountId,
     pub key: String,
-    pub value: serde_json::Value,
+    pub get_value_after_getting_network_callback: GetValueAfterGettingNetworkCallback,
+    pub skip_confirmation: bool,
+    /// Remove the stored leaves under the key that are missing from the value, so the value
+    /// replaces the stored data instead of being merged into it.
+    pub replace_existing: bool,
+}
+
+/// Returns the value to set once the network is selected (e.g. data copied from another account).
+pub type GetValueAfterGettingNetworkCallback = std::sync::Arc<
+    dyn Fn(&near_cli_rs::config::NetworkConfig) -> color_eyre::eyre::Result<serde_json::Value>,
+>;
+
+/// The value given on the command line or read from a file.
+pub fn fixed_value(value: serde_json::Value) -> GetValueAfterGettingNetworkCallback {
+    std::sync::Arc::new(move |_network_config| Ok(value.clone()))
 }
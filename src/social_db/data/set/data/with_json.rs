@@ -21,7 +21,9 @@ impl JsonDataContext {
             global_context: previous_context.global_context,
             set_to_account_id: previous_context.set_to_account_id,
             key: previous_context.key,
-            value: scope.args.clone().into(),
+            skip_confirmation: previous_context.skip_confirmation,
+            replace_existing: false,
+            get_value_after_getting_network_callback: super::fixed_value(scope.args.clone().into()),
         }))
     }
 }
@@ -30,7 +30,9 @@ impl JsonDataFileContext {
             global_context: previous_context.global_context,
             set_to_account_id: previous_context.set_to_account_id,
             key: previous_context.key,
-            value,
+            skip_confirmation: previous_context.skip_confirmation,
+            replace_existing: false,
+            get_value_after_getting_network_callback: super::fixed_value(value),
         }))
     }
 }
@@ -56,12 +58,12 @@ impl JsonDataFile {
                 )
                 .is_err()
                 {
-                    println!("File data is not in JSON format!");
+                    crate::human_println!("File data is not in JSON format!");
                 } else {
                     return Ok(Some(path));
                 }
             } else {
-                println!("Access to data file <{:?}> not found!", path)
+                crate::human_println!("Access to data file <{:?}> not found!", path)
             }
         }
     }
@@ -26,7 +26,9 @@ impl TextDataFileContext {
             global_context: previous_context.global_context,
             set_to_account_id: previous_context.set_to_account_id,
             key: previous_context.key,
-            value,
+            skip_confirmation: previous_context.skip_confirmation,
+            replace_existing: false,
+            get_value_after_getting_network_callback: super::fixed_value(value),
         }))
     }
 }
@@ -2,8 +2,8 @@ use std::str::FromStr;
 
 use color_eyre::eyre::ContextCompat;
 
-mod data;
-mod sign_as;
+pub mod data;
+pub mod sign_as;
 
 #[derive(Debug, Clone, interactive_clap::InteractiveClap)]
 #[interactive_clap(input_context = near_cli_rs::GlobalContext)]
@@ -11,6 +11,9 @@ mod sign_as;
 pub struct Set {
     /// Enter SocialDB key path to set the value (e.g. root.near/profile/name):
     key: String,
+    #[interactive_clap(long)]
+    /// Skip the preview confirmation and sign the transaction right away
+    yes: bool,
     #[interactive_clap(subcommand)]
     data_type: self::data::DataType,
 }
@@ -20,6 +23,7 @@ pub struct SetContext {
     pub global_context: near_cli_rs::GlobalContext,
     pub set_to_account_id: near_cli_rs::types::account_id::AccountId,
     pub key: String,
+    pub skip_confirmation: bool,
 }
 
 impl SetContext {
@@ -38,6 +42,7 @@ impl SetContext {
                     .trim(),
             )?,
             key: scope.key.clone(),
+            skip_confirmation: scope.yes,
         })
     }
 }
@@ -1,6 +1,6 @@
-use color_eyre::eyre::{ContextCompat, WrapErr};
+use color_eyre::eyre::WrapErr;
 use inquire::{CustomType, Select};
-use near_cli_rs::common::{CallResultExt, JsonRpcClientExt};
+use near_cli_rs::common::CallResultExt;
 use std::sync::Arc;
 
 #[derive(Debug, Clone, interactive_clap::InteractiveClap)]
@@ -10,6 +10,14 @@ pub struct Signer {
     #[interactive_clap(skip_default_input_arg)]
     /// What is the signer account ID?
     signer_account_id: near_cli_rs::types::account_id::AccountId,
+    /// Write the unsigned transaction to this file instead of signing it (to sign it offline)
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    export_unsigned_transaction: Option<near_cli_rs::types::path_buf::PathBuf>,
+    /// The public key of the signer access key that will sign the exported transaction
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    signer_public_key: Option<near_cli_rs::types::public_key::PublicKey>,
     #[interactive_clap(named_arg)]
     /// Select network
     network_config: near_cli_rs::network_for_transaction::NetworkForTransactionArgs,
@@ -27,27 +35,71 @@ impl SignerContext {
             previous_context.set_to_account_id.clone().into();
         let signer_id: near_primitives::types::AccountId = scope.signer_account_id.clone().into();
         let key = previous_context.key.clone();
+        let export_unsigned_transaction =
+            match (&scope.export_unsigned_transaction, &scope.signer_public_key) {
+                (Some(path), Some(public_key)) => Some((path.0.clone(), public_key.0.clone())),
+                (Some(_), None) => color_eyre::eyre::bail!(
+                    "--signer-public-key is required to export the unsigned transaction"
+                ),
+                (None, _) => None,
+            };
+
+        let on_before_signing_callback: near_cli_rs::commands::OnBeforeSigningCallback =
+            Arc::new({
+                let signer_id = signer_id.clone();
+                let set_to_account_id = set_to_account_id.clone();
+                let key = key.clone();
+
+                move |prepopulated_unsigned_transaction, network_config| {
+                    let json_rpc_client = network_config.json_rpc_client();
+                    let public_key = prepopulated_unsigned_transaction.public_key.clone();
+                    let receiver_id = prepopulated_unsigned_transaction.receiver_id.clone();
+                    if let Some(near_primitives::transaction::Action::FunctionCall(action)) =
+                        prepopulated_unsigned_transaction.actions.get_mut(0)
+                    {
+                        action.deposit =
+                            crate::common::block_on(near_socialdb_client::get_deposit(
+                                &json_rpc_client,
+                                &signer_id,
+                                &public_key,
+                                &set_to_account_id,
+                                &key,
+                                &receiver_id,
+                                near_cli_rs::types::near_token::NearToken::from_yoctonear(
+                                    action.deposit,
+                                )
+                                .into(),
+                            ))?
+                            .as_yoctonear();
+                        Ok(())
+                    } else {
+                        color_eyre::eyre::bail!("Unexpected action to change components",);
+                    }
+                }
+            });
 
         let get_prepopulated_transaction_after_getting_network_callback: near_cli_rs::commands::GetPrepopulatedTransactionAfterGettingNetworkCallback = Arc::new({
             let signer_id = signer_id.clone();
             let set_to_account_id = set_to_account_id.clone();
+            let on_before_signing_callback = on_before_signing_callback.clone();
+            let config = previous_context.global_context.config.clone();
 
             move |network_config| {
-                let near_social_account_id = crate::consts::NEAR_SOCIAL_ACCOUNT_ID.get(network_config.network_name.as_str())
-                    .wrap_err_with(|| format!("The <{}> network does not have a near-social contract.", network_config.network_name))?;
+                let near_social_account_id = &crate::common::get_near_social_account_id(network_config)?;
                 let key = previous_context.key.clone();
                 let input_args = serde_json::to_string(&crate::socialdb_types::SocialDbQuery {
-                    keys: vec![format!("{key}")],
+                    keys: vec![key.clone(), format!("{key}/**")],
+                    options: None,
                 })
                 .wrap_err("Internal error: could not serialize SocialDB input args")?;
 
-                let remote_social_db_data_for_key: serde_json::Value = network_config
-                    .json_rpc_client()
-                    .blocking_call_view_function(
+                let remote_social_db_data_for_key: serde_json::Value = crate::common::call_view_function_with_failover(
+                        network_config,
                         near_social_account_id,
                         "get",
                         input_args.into_bytes(),
                         near_primitives::types::Finality::Final.into(),
+                        crate::cache::CachePolicy::Bypass,
                     )
                     .wrap_err("Failed to fetch the components from SocialDB")?
                     .parse_result_from_json()
@@ -60,23 +112,89 @@ impl SignerContext {
                         Some(&remote_social_db_data_for_key)
                     };
 
-                let mut social_db_data_to_set = previous_context.value.clone();
+                let mut social_db_data_to_set = (previous_context.get_value_after_getting_network_callback)(network_config)?;
 
                 crate::common::social_db_data_from_key(&key, &mut social_db_data_to_set);
 
+                if let (true, Some(remote_social_db_data)) = (previous_context.replace_existing, optional_remote_social_db_data_for_key) {
+                    let mut replaced_social_db_data = remote_social_db_data.clone();
+                    crate::common::mark_leaf_values_as_null(&mut replaced_social_db_data);
+                    crate::common::merge_social_db_data(&mut replaced_social_db_data, &social_db_data_to_set);
+                    social_db_data_to_set = replaced_social_db_data;
+                }
+
                 let json_rpc_client = network_config.json_rpc_client();
 
-                let deposit = tokio::runtime::Runtime::new().unwrap().block_on(
+                let deposit = crate::common::block_on(
                     near_socialdb_client::required_deposit(
                         &json_rpc_client,
                         near_social_account_id,
                         &set_to_account_id,
-                        &social_db_data_to_set,
+                        // The removed (`null`) values only release storage
+                        &crate::common::without_null_leaves(&social_db_data_to_set)
+                            .unwrap_or_else(|| serde_json::json!({})),
                         optional_remote_social_db_data_for_key,
                     ),
                 )?;
 
-                Ok(near_cli_rs::commands::PrepopulatedTransaction {
+                let social_db_data_diff = crate::common::diff_social_db_data(
+                    optional_remote_social_db_data_for_key,
+                    &social_db_data_to_set,
+                );
+                if !social_db_data_diff.has_changes() {
+                    crate::human_println!("\nThe data for the key <{key}> is already up to date. Goodbye.");
+                    return Ok(near_cli_rs::commands::PrepopulatedTransaction {
+                        signer_id: signer_id.clone(),
+                        receiver_id: near_social_account_id.clone(),
+                        actions: vec![],
+                    });
+                }
+                let storage_balance = crate::common::get_storage_balance(
+                    network_config,
+                    near_social_account_id,
+                    &set_to_account_id,
+                )?;
+                crate::human_println!("\nThe following changes will be applied to SocialDB:");
+                crate::human_println!(
+                    "{}",
+                    serde_json::to_string_pretty(&serde_json::json!({
+                        "changes": social_db_data_diff,
+                        "deposit": {
+                            "storage_balance_total": storage_balance.as_ref().map(|storage_balance| {
+                                near_cli_rs::types::near_token::NearToken::from_yoctonear(storage_balance.total).to_string()
+                            }),
+                            "storage_balance_available": storage_balance.as_ref().map(|storage_balance| {
+                                near_cli_rs::types::near_token::NearToken::from_yoctonear(storage_balance.available).to_string()
+                            }),
+                            "required_deposit": near_cli_rs::types::near_token::NearToken::from_yoctonear(deposit.as_yoctonear()).to_string(),
+                        },
+                    }))?
+                );
+
+                if !previous_context.skip_confirmation {
+                    #[derive(strum_macros::Display)]
+                    enum ConfirmOptions {
+                        #[strum(to_string = "Yes, I want to sign the transaction with these changes.")]
+                        Yes,
+                        #[strum(to_string = "No, I don't want to change anything.")]
+                        No,
+                    }
+                    let select_choose_input = Select::new(
+                        "Do you want to apply these changes to SocialDB?",
+                        vec![ConfirmOptions::Yes, ConfirmOptions::No],
+                    )
+                    .prompt()?;
+                    if let ConfirmOptions::No = select_choose_input {
+                        crate::human_println!("The changes were not applied. Goodbye.");
+                        return Ok(near_cli_rs::commands::PrepopulatedTransaction {
+                            signer_id: signer_id.clone(),
+                            receiver_id: near_social_account_id.clone(),
+                            actions: vec![],
+                        });
+                    }
+                }
+
+                let mut prepopulated_transaction = near_cli_rs::commands::PrepopulatedTransaction {
                     signer_id: signer_id.clone(),
                     receiver_id: near_social_account_id.clone(),
                     actions: vec![
@@ -90,51 +208,31 @@ impl SignerContext {
                             deposit: deposit.as_yoctonear(),
                         }),
                     )
-                ]})
-            }
-        });
+                ]};
 
-        let on_before_signing_callback: near_cli_rs::commands::OnBeforeSigningCallback =
-            Arc::new({
-                let set_to_account_id = set_to_account_id.clone();
-
-                move |prepopulated_unsigned_transaction, network_config| {
-                    let json_rpc_client = network_config.json_rpc_client();
-                    let public_key = prepopulated_unsigned_transaction.public_key.clone();
-                    let receiver_id = prepopulated_unsigned_transaction.receiver_id.clone();
-                    if let Some(near_primitives::transaction::Action::FunctionCall(action)) =
-                        prepopulated_unsigned_transaction.actions.get_mut(0)
-                    {
-                        action.deposit = tokio::runtime::Runtime::new()
-                            .unwrap()
-                            .block_on(near_socialdb_client::get_deposit(
-                                &json_rpc_client,
-                                &signer_id,
-                                &public_key,
-                                &set_to_account_id,
-                                &key,
-                                &receiver_id,
-                                near_cli_rs::types::near_token::NearToken::from_yoctonear(
-                                    action.deposit,
-                                )
-                                .into(),
-                            ))?
-                            .as_yoctonear();
-                        Ok(())
-                    } else {
-                        color_eyre::eyre::bail!("Unexpected action to change components",);
-                    }
+                if let Some((path, signer_public_key)) = &export_unsigned_transaction {
+                    crate::transaction::unsigned::export(
+                        path,
+                        &config,
+                        network_config,
+                        &prepopulated_transaction,
+                        signer_public_key,
+                        &on_before_signing_callback,
+                        crate::transaction::unsigned::Operation::DataSet {
+                            set_to_account_id: set_to_account_id.clone(),
+                        },
+                    )?;
+                    prepopulated_transaction.actions = vec![];
                 }
-            });
+
+                Ok(prepopulated_transaction)
+            }
+        });
 
         let on_after_sending_transaction_callback: near_cli_rs::transaction_signature_options::OnAfterSendingTransactionCallback = std::sync::Arc::new({
                 move |transaction_info, _network_config| {
-                    if let near_primitives::views::FinalExecutionStatus::SuccessValue(_) = transaction_info.status {
-                        println!("Keys successfully installed on <{set_to_account_id}>");
-                    } else {
-                        color_eyre::eyre::bail!("Keys were not successfully installed on <{set_to_account_id}>");
-                    };
-                    Ok(())
+                    crate::cache::invalidate_final_block_entries();
+                    crate::common::print_data_set_status(transaction_info, &set_to_account_id)
                 }
             });
 
@@ -170,7 +268,7 @@ impl Signer {
                 &context.global_context.config.network_connection,
                 signer_account_id.clone().into(),
             ) {
-                println!("\nThe account <{signer_account_id}> does not yet exist.");
+                crate::human_println!("\nThe account <{signer_account_id}> does not yet exist.");
                 #[derive(strum_macros::Display)]
                 enum ConfirmOptions {
                     #[strum(to_string = "Yes, I want to enter a new account name.")]
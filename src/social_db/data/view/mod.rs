@@ -1,5 +1,5 @@
 use color_eyre::eyre::WrapErr;
-use near_cli_rs::common::{CallResultExt, JsonRpcClientExt};
+use near_cli_rs::common::CallResultExt;
 
 #[derive(Debug, Clone, interactive_clap::InteractiveClap)]
 #[interactive_clap(input_context = near_cli_rs::GlobalContext)]
@@ -7,74 +7,136 @@ use near_cli_rs::common::{CallResultExt, JsonRpcClientExt};
 pub struct View {
     /// Enter SocialDB key path to view (e.g. root.near/profile/**):
     key: String,
+    #[interactive_clap(long)]
+    /// Annotate every value with the block height it was written at
+    with_block_height: bool,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// Output format: json (default), compact-json, yaml, flat (`key/path = value` per line), raw (a single value, unquoted) or tree
+    format: Option<ViewOutputFormat>,
     #[interactive_clap(named_arg)]
     /// Select network
-    network_config: near_cli_rs::network::Network,
+    network_config: near_cli_rs::network_view_at_block::NetworkViewAtBlockArgs,
 }
 
 #[derive(Clone)]
-pub struct ViewContext(near_cli_rs::network::NetworkContext);
+pub struct ViewContext(near_cli_rs::network_view_at_block::ArgsForViewContext);
 
 impl ViewContext {
     pub fn from_previous_context(
         previous_context: near_cli_rs::GlobalContext,
         scope: &<View as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
     ) -> color_eyre::eyre::Result<Self> {
-        let on_after_getting_network_callback: near_cli_rs::network::OnAfterGettingNetworkCallback =
+        let on_after_getting_block_reference_callback: near_cli_rs::network_view_at_block::OnAfterGettingBlockReferenceCallback =
             std::sync::Arc::new({
                 let key = scope.key.clone();
+                let with_block_height = scope.with_block_height;
+                let format = scope.format.unwrap_or_default();
 
-                move |network_config| {
-                    let near_social_account_id = match crate::consts::NEAR_SOCIAL_ACCOUNT_ID
-                        .get(&network_config.network_name.as_str())
-                    {
-                        Some(account_id) => account_id,
-                        None => {
-                            return Err(color_eyre::Report::msg(format!(
-                                "The <{}> network does not have a near-social contract.",
-                                network_config.network_name
-                            )))
-                        }
-                    };
+                move |network_config, block_reference| {
+                    let near_social_account_id = &crate::common::get_near_social_account_id(network_config)?;
 
                     let input_args = serde_json::to_string(&crate::socialdb_types::SocialDbQuery {
                         keys: vec![format!("{key}")],
+                        options: with_block_height.then_some(crate::socialdb_types::SocialDbGetOptions {
+                            with_block_height: Some(true),
+                        }),
                     })
                     .wrap_err("Internal error: could not serialize SocialDB input args")?;
 
-                    let call_result = network_config
-                        .json_rpc_client()
-                        .blocking_call_view_function(
-                            near_social_account_id,
-                            "get",
-                            input_args.into_bytes(),
-                            near_primitives::types::Finality::Final.into(),
-                        )
-                        .wrap_err("Failed to fetch the widgets state from SocialDB")?;
+                    let call_result = crate::common::call_view_function_with_failover(
+                        network_config,
+                        near_social_account_id,
+                        "get",
+                        input_args.into_bytes(),
+                        block_reference.clone(),
+                        crate::cache::CachePolicy::Use,
+                    )
+                    .wrap_err("Failed to fetch the widgets state from SocialDB")?;
                     if call_result.result.is_empty() {
                         eprintln!("There is no information for this request");
+                        crate::output::emit(&serde_json::Value::Null)?;
                     } else if let Ok(json_result) =
                         call_result.parse_result_from_json::<serde_json::Value>()
                     {
-                        println!("{}", serde_json::to_string_pretty(&json_result)?);
+                        if crate::output::is_json() {
+                            crate::output::emit(&json_result)?;
+                        } else {
+                            print_social_db_data(&json_result, format)?;
+                        }
                     } else if let Ok(string_result) = String::from_utf8(call_result.result) {
-                        println!("{string_result}");
+                        if crate::output::is_json() {
+                            crate::output::emit(&string_result)?;
+                        } else {
+                            println!("{string_result}");
+                        }
                     } else {
                         eprintln!("The returned value is not printable (binary data)");
                     }
                     Ok(())
                 }
             });
-        Ok(Self(near_cli_rs::network::NetworkContext {
-            config: previous_context.config,
-            interacting_with_account_ids: vec![],
-            on_after_getting_network_callback,
-        }))
+        Ok(Self(
+            near_cli_rs::network_view_at_block::ArgsForViewContext {
+                config: previous_context.config,
+                interacting_with_account_ids: vec![],
+                on_after_getting_block_reference_callback,
+            },
+        ))
     }
 }
 
-impl From<ViewContext> for near_cli_rs::network::NetworkContext {
+impl From<ViewContext> for near_cli_rs::network_view_at_block::ArgsForViewContext {
     fn from(item: ViewContext) -> Self {
         item.0
     }
 }
+
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, strum_macros::Display, strum_macros::EnumString,
+)]
+#[strum(serialize_all = "kebab-case", ascii_case_insensitive)]
+pub enum ViewOutputFormat {
+    #[default]
+    Json,
+    CompactJson,
+    Yaml,
+    Flat,
+    Raw,
+    Tree,
+}
+
+impl interactive_clap::ToCli for ViewOutputFormat {
+    type CliVariant = ViewOutputFormat;
+}
+
+pub fn print_social_db_data(
+    data: &serde_json::Value,
+    format: ViewOutputFormat,
+) -> color_eyre::eyre::Result<()> {
+    match format {
+        ViewOutputFormat::Json => println!("{}", serde_json::to_string_pretty(data)?),
+        ViewOutputFormat::CompactJson => println!("{}", serde_json::to_string(data)?),
+        ViewOutputFormat::Yaml => print!("{}", serde_yaml::to_string(data)?),
+        ViewOutputFormat::Flat => {
+            for (path, value) in crate::common::social_db_data_leaves(data) {
+                println!("{path} = {value}");
+            }
+        }
+        ViewOutputFormat::Raw => {
+            let leaves = crate::common::social_db_data_leaves(data);
+            let [(_, value)] = leaves.as_slice() else {
+                return Err(color_eyre::Report::msg(format!(
+                    "The raw output format requires the key to resolve to a single value, but {} values were found",
+                    leaves.len()
+                )));
+            };
+            match value {
+                serde_json::Value::String(value) => print!("{value}"),
+                value => print!("{value}"),
+            }
+        }
+        ViewOutputFormat::Tree => print!("{}", crate::common::social_db_data_tree(data, true)),
+    }
+    Ok(())
+}
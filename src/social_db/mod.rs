@@ -23,9 +23,9 @@ pub enum SocialDbCommand {
     /// Data management: viewing, adding, updating, deleting information by a given key
     Data(self::data::Data),
     #[strum_discriminants(strum(
-        message = "manage-profile    -   Profile management: view, update"
+        message = "manage-profile    -   Profile management: view, update, apply, copy"
     ))]
-    /// Profile management: view, update
+    /// Profile management: view, update, apply, copy
     ManageProfile(self::profile_management::ManageProfile),
     #[strum_discriminants(strum(
         message = "prepaid-storage   -   Storage management: deposit, withdrawal, balance review"
@@ -0,0 +1,89 @@
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = near_cli_rs::GlobalContext)]
+#[interactive_clap(output_context = CheckContext)]
+pub struct Check {
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// The public key of the function-call access key to check
+    public_key: Option<near_cli_rs::types::public_key::PublicKey>,
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    /// The account ID to check
+    predecessor: Option<near_cli_rs::types::account_id::AccountId>,
+    #[interactive_clap(long)]
+    /// Enter a comma-separated list of SocialDB keys to check (e.g. frol.near/widget/**):
+    key: near_cli_rs::types::vec_string::VecString,
+    #[interactive_clap(named_arg)]
+    /// Select network
+    network_config: near_cli_rs::network::Network,
+}
+
+#[derive(Clone)]
+pub struct CheckContext(near_cli_rs::network::NetworkContext);
+
+impl CheckContext {
+    pub fn from_previous_context(
+        previous_context: near_cli_rs::GlobalContext,
+        scope: &<Check as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let permission_key: near_socialdb_client::PermissionKey =
+            match (&scope.public_key, &scope.predecessor) {
+                (Some(public_key), None) => public_key.0.clone().into(),
+                (None, Some(predecessor)) => predecessor.0.clone().into(),
+                _ => color_eyre::eyre::bail!(
+                    "Exactly one of `--public-key` or `--predecessor` must be specified"
+                ),
+            };
+        let keys = scope.key.0.clone();
+
+        let on_after_getting_network_callback: near_cli_rs::network::OnAfterGettingNetworkCallback =
+            std::sync::Arc::new(move |network_config| {
+                let near_social_account_id =
+                    &crate::common::get_near_social_account_id(network_config)?;
+
+                let json_rpc_client = network_config.json_rpc_client();
+                let mut not_granted_count = 0;
+                let mut results = serde_json::Map::new();
+                for key in &keys {
+                    let is_granted = crate::common::block_on(
+                        near_socialdb_client::is_write_permission_granted(
+                            &json_rpc_client,
+                            near_social_account_id,
+                            permission_key.clone(),
+                            key.clone(),
+                        ),
+                    )?;
+                    if !is_granted {
+                        not_granted_count += 1;
+                    }
+                    crate::human_println!("{key}: {}", if is_granted { "yes" } else { "no" });
+                    results.insert(key.clone(), is_granted.into());
+                }
+                crate::output::emit(&results)?;
+
+                if not_granted_count > 0 {
+                    color_eyre::eyre::bail!(
+                        "Write permission is not granted for {not_granted_count} of {} key(s)",
+                        keys.len()
+                    );
+                }
+                Ok(())
+            });
+
+        Ok(Self(near_cli_rs::network::NetworkContext {
+            config: previous_context.config,
+            interacting_with_account_ids: scope
+                .predecessor
+                .iter()
+                .map(|predecessor| predecessor.clone().into())
+                .collect(),
+            on_after_getting_network_callback,
+        }))
+    }
+}
+
+impl From<CheckContext> for near_cli_rs::network::NetworkContext {
+    fn from(item: CheckContext) -> Self {
+        item.0
+    }
+}
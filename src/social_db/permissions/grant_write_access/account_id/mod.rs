@@ -23,7 +23,7 @@ impl AccessToAccountContext {
     ) -> color_eyre::eyre::Result<Self> {
         Ok(Self(super::storage_deposit::AccessToPermissionKeyContext {
             global_context: previous_context.global_context,
-            social_db_key: previous_context.social_db_key,
+            social_db_keys: previous_context.social_db_keys,
             permission_key: scope.account_id.0.clone().into(),
         }))
     }
@@ -50,7 +50,7 @@ impl AccessToAccount {
                 &context.global_context.config.network_connection,
                 deploy_to_account_id.clone().into(),
             ) {
-                println!(
+                crate::human_println!(
                     "\nThe account <{}> does not yet exist.",
                     &deploy_to_account_id
                 );
@@ -19,7 +19,7 @@ impl AccessToPublicKeyContext {
     ) -> color_eyre::eyre::Result<Self> {
         Ok(Self(super::storage_deposit::AccessToPermissionKeyContext {
             global_context: previous_context.global_context,
-            social_db_key: previous_context.social_db_key,
+            social_db_keys: previous_context.social_db_keys,
             permission_key: scope.public_key.0.clone().into(),
         }))
     }
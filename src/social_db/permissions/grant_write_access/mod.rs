@@ -11,8 +11,8 @@ mod storage_deposit;
 #[interactive_clap(output_context = SocialDbKeyContext)]
 pub struct SocialDbKey {
     #[interactive_clap(skip_default_input_arg)]
-    /// Enter the prefix of the social_db key that you will grant permission to (default value: 'widget'):
-    social_db_key: String,
+    /// Enter a comma-separated list of the social_db key prefixes that you will grant permission to (default value: 'widget'):
+    social_db_keys: near_cli_rs::types::vec_string::VecString,
     #[interactive_clap(subcommand)]
     access: Access,
 }
@@ -20,7 +20,7 @@ pub struct SocialDbKey {
 #[derive(Clone)]
 pub struct SocialDbKeyContext {
     pub global_context: near_cli_rs::GlobalContext,
-    pub social_db_key: String,
+    pub social_db_keys: Vec<String>,
 }
 
 impl SocialDbKeyContext {
@@ -30,19 +30,20 @@ impl SocialDbKeyContext {
     ) -> color_eyre::eyre::Result<Self> {
         Ok(Self {
             global_context: previous_context,
-            social_db_key: scope.social_db_key.clone(),
+            social_db_keys: scope.social_db_keys.clone().into(),
         })
     }
 }
 
 impl SocialDbKey {
-    fn input_social_db_key(
+    fn input_social_db_keys(
         _context: &near_cli_rs::GlobalContext,
-    ) -> color_eyre::eyre::Result<Option<String>> {
+    ) -> color_eyre::eyre::Result<Option<near_cli_rs::types::vec_string::VecString>> {
         Ok(Some(
-            Text::new("Enter the prefix of the social_db key that you will grant permission to (default value: 'widget'):")
+            Text::new("Enter a comma-separated list of the social_db key prefixes that you will grant permission to (default value: 'widget'):")
                 .with_default("widget")
-                .prompt()?,
+                .prompt()?
+                .parse()?,
         ))
     }
 }
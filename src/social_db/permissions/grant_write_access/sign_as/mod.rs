@@ -1,5 +1,3 @@
-use color_eyre::eyre::ContextCompat;
-
 #[derive(Debug, Clone, interactive_clap::InteractiveClap)]
 #[interactive_clap(input_context = super::storage_deposit::ExtraStorageDepositContext)]
 #[interactive_clap(output_context = SignerContext)]
@@ -26,15 +24,24 @@ impl SignerContext {
         previous_context: super::storage_deposit::ExtraStorageDepositContext,
         scope: &<Signer as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
     ) -> color_eyre::eyre::Result<Self> {
+        let signer_account_id: near_primitives::types::AccountId =
+            scope.signer_account_id.clone().into();
+        let social_db_keys = previous_context
+            .social_db_keys
+            .iter()
+            .map(|social_db_key| {
+                crate::common::social_db_key_for_signer(&signer_account_id, social_db_key)
+            })
+            .collect::<color_eyre::eyre::Result<Vec<_>>>()?;
+        if social_db_keys.is_empty() {
+            color_eyre::eyre::bail!("There are no social_db keys to grant permission to");
+        }
         Ok(Self {
             global_context: previous_context.global_context,
-            social_db_keys: vec![format!(
-                "{}/{}",
-                scope.signer_account_id, previous_context.social_db_key
-            )],
+            social_db_keys,
             permission_key: previous_context.permission_key,
             extra_storage_deposit: previous_context.extra_storage_deposit,
-            signer_account_id: scope.signer_account_id.clone().into(),
+            signer_account_id,
         })
     }
 }
@@ -48,8 +55,11 @@ impl From<SignerContext> for near_cli_rs::commands::ActionContext {
 
         let get_prepopulated_transaction_after_getting_network_callback: near_cli_rs::commands::GetPrepopulatedTransactionAfterGettingNetworkCallback = std::sync::Arc::new({
             move |network_config| {
-                let near_social_account_id = crate::consts::NEAR_SOCIAL_ACCOUNT_ID.get(network_config.network_name.as_str())
-                    .wrap_err_with(|| format!("The <{}> network does not have a near-social contract.", network_config.network_name))?;
+                let near_social_account_id = &crate::common::get_near_social_account_id(network_config)?;
+                eprintln!("Write permission will be granted to the following keys:");
+                for social_db_key in &social_db_keys {
+                    eprintln!("  {social_db_key}");
+                }
                 let args = match &permission_key {
                     near_socialdb_client::PermissionKey::PredecessorId(account_id) => {
                         serde_json::json!({
@@ -115,7 +125,19 @@ impl From<SignerContext> for near_cli_rs::commands::ActionContext {
                     }
                 };
 
-                Ok(())
+                let grantee = match &permission_key {
+                    near_socialdb_client::PermissionKey::PredecessorId(account_id) => {
+                        serde_json::json!({ "account_id": account_id })
+                    }
+                    near_socialdb_client::PermissionKey::PublicKey(public_key) => {
+                        serde_json::json!({ "public_key": public_key })
+                    }
+                };
+                crate::output::emit(&serde_json::json!({
+                    "transaction_hash": transaction_info.transaction_outcome.id,
+                    "signer_account_id": signer_id,
+                    "granted_to": grantee,
+                }))
             }
         });
 
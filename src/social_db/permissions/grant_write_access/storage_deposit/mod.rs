@@ -16,7 +16,7 @@ pub struct ExtraStorageDeposit {
 #[derive(Clone)]
 pub struct ExtraStorageDepositContext {
     pub global_context: near_cli_rs::GlobalContext,
-    pub social_db_key: String,
+    pub social_db_keys: Vec<String>,
     pub permission_key: near_socialdb_client::PermissionKey,
     pub extra_storage_deposit: near_cli_rs::types::near_token::NearToken,
 }
@@ -28,7 +28,7 @@ impl ExtraStorageDepositContext {
     ) -> color_eyre::eyre::Result<Self> {
         Ok(Self {
             global_context: previous_context.global_context,
-            social_db_key: previous_context.social_db_key,
+            social_db_keys: previous_context.social_db_keys,
             permission_key: previous_context.permission_key,
             extra_storage_deposit: scope.extra_storage_deposit,
         })
@@ -54,6 +54,6 @@ impl ExtraStorageDeposit {
 #[derive(Clone)]
 pub struct AccessToPermissionKeyContext {
     pub global_context: near_cli_rs::GlobalContext,
-    pub social_db_key: String,
+    pub social_db_keys: Vec<String>,
     pub permission_key: near_socialdb_client::PermissionKey,
 }
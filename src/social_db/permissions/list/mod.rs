@@ -0,0 +1,160 @@
+use color_eyre::eyre::WrapErr;
+use inquire::Text;
+use near_cli_rs::common::{JsonRpcClientExt, RpcQueryResponseExt};
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = near_cli_rs::GlobalContext)]
+#[interactive_clap(output_context = ListContext)]
+pub struct List {
+    #[interactive_clap(skip_default_input_arg)]
+    /// For which account do you want to list the granted write permissions?
+    account_id: near_cli_rs::types::account_id::AccountId,
+    #[interactive_clap(skip_default_input_arg)]
+    /// Enter the prefix of the social_db key to check the permissions for (default value: 'widget'):
+    social_db_key: String,
+    #[interactive_clap(named_arg)]
+    /// Select network
+    network_config: near_cli_rs::network::Network,
+}
+
+#[derive(Clone)]
+pub struct ListContext(near_cli_rs::network::NetworkContext);
+
+impl ListContext {
+    pub fn from_previous_context(
+        previous_context: near_cli_rs::GlobalContext,
+        scope: &<List as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let account_id: near_primitives::types::AccountId = scope.account_id.clone().into();
+        let social_db_key = format!("{account_id}/{}", scope.social_db_key);
+        let known_account_ids = near_cli_rs::common::get_used_account_list(
+            &previous_context.config.credentials_home_dir,
+        )
+        .into_iter()
+        .map(|used_account| used_account.account_id)
+        .filter(|known_account_id| known_account_id != &account_id)
+        .collect::<Vec<_>>();
+
+        let on_after_getting_network_callback: near_cli_rs::network::OnAfterGettingNetworkCallback =
+            std::sync::Arc::new({
+                let account_id = account_id.clone();
+
+                move |network_config| {
+                    let near_social_account_id =
+                        &crate::common::get_near_social_account_id(network_config)?;
+
+                    let access_key_list = network_config
+                        .json_rpc_client()
+                        .blocking_call_view_access_key_list(
+                            &account_id,
+                            near_primitives::types::Finality::Final.into(),
+                        )
+                        .wrap_err_with(|| {
+                            format!("Failed to fetch the access key list for <{account_id}>")
+                        })?
+                        .access_key_list_view()?;
+                    let function_call_public_keys = access_key_list
+                        .keys
+                        .into_iter()
+                        .filter(|access_key| {
+                            near_socialdb_client::is_signer_access_key_function_call_access_can_call_set_on_social_db_account(
+                                near_social_account_id,
+                                &access_key.access_key.permission,
+                            )
+                            .unwrap_or(false)
+                        })
+                        .map(|access_key| access_key.public_key);
+
+                    let permission_keys = function_call_public_keys
+                        .map(near_socialdb_client::PermissionKey::from)
+                        .chain(
+                            known_account_ids
+                                .iter()
+                                .cloned()
+                                .map(near_socialdb_client::PermissionKey::from),
+                        )
+                        .collect::<Vec<_>>();
+
+                    let json_rpc_client = network_config.json_rpc_client();
+                    let mut granted_permission_keys = Vec::new();
+                    for permission_key in permission_keys {
+                        if crate::common::block_on(
+                            near_socialdb_client::is_write_permission_granted(
+                                &json_rpc_client,
+                                near_social_account_id,
+                                permission_key.clone(),
+                                social_db_key.clone(),
+                            ),
+                        )? {
+                            granted_permission_keys.push(permission_key);
+                        }
+                    }
+
+                    let mut granted_account_ids = Vec::new();
+                    let mut granted_public_keys = Vec::new();
+                    for permission_key in granted_permission_keys {
+                        match permission_key {
+                            near_socialdb_client::PermissionKey::PredecessorId(account_id) => {
+                                granted_account_ids.push(account_id);
+                            }
+                            near_socialdb_client::PermissionKey::PublicKey(public_key) => {
+                                granted_public_keys.push(public_key);
+                            }
+                        }
+                    }
+
+                    if granted_account_ids.is_empty() && granted_public_keys.is_empty() {
+                        eprintln!(
+                            "No function-call access keys of <{account_id}> or known accounts have write permission to <{social_db_key}>"
+                        );
+                    } else {
+                        eprintln!("Write permission to <{social_db_key}> is granted to:");
+                        for public_key in &granted_public_keys {
+                            crate::human_println!("  public key   {public_key}");
+                        }
+                        for account_id in &granted_account_ids {
+                            crate::human_println!("  account      {account_id}");
+                        }
+                    }
+                    crate::output::emit(&serde_json::json!({
+                        "key": social_db_key,
+                        "accounts": granted_account_ids,
+                        "public_keys": granted_public_keys,
+                    }))
+                }
+            });
+
+        Ok(Self(near_cli_rs::network::NetworkContext {
+            config: previous_context.config,
+            interacting_with_account_ids: vec![account_id],
+            on_after_getting_network_callback,
+        }))
+    }
+}
+
+impl From<ListContext> for near_cli_rs::network::NetworkContext {
+    fn from(item: ListContext) -> Self {
+        item.0
+    }
+}
+
+impl List {
+    pub fn input_account_id(
+        context: &near_cli_rs::GlobalContext,
+    ) -> color_eyre::eyre::Result<Option<near_cli_rs::types::account_id::AccountId>> {
+        near_cli_rs::common::input_non_signer_account_id_from_used_account_list(
+            &context.config.credentials_home_dir,
+            "For which account do you want to list the granted write permissions?",
+        )
+    }
+
+    fn input_social_db_key(
+        _context: &near_cli_rs::GlobalContext,
+    ) -> color_eyre::eyre::Result<Option<String>> {
+        Ok(Some(
+            Text::new("Enter the prefix of the social_db key to check the permissions for (default value: 'widget'):")
+                .with_default("widget")
+                .prompt()?,
+        ))
+    }
+}
@@ -1,6 +1,9 @@
 use strum::{EnumDiscriminants, EnumIter, EnumMessage};
 
+mod check;
 mod grant_write_access;
+mod list;
+mod revoke_write_access;
 
 #[derive(Debug, Clone, interactive_clap::InteractiveClap)]
 #[interactive_clap(context = near_cli_rs::GlobalContext)]
@@ -19,4 +22,19 @@ pub enum PermissionsCommand {
     ))]
     /// Granting access to a function-call-only access key or a different account
     GrantWriteAccess(self::grant_write_access::SocialDbKey),
+    #[strum_discriminants(strum(
+        message = "revoke-write-access  -   Revoking access from a function-call-only access key (the key is deleted)"
+    ))]
+    /// Revoking access from a function-call-only access key (the key is deleted)
+    RevokeWriteAccess(self::revoke_write_access::RevokeWriteAccess),
+    #[strum_discriminants(strum(
+        message = "list                 -   Listing the function-call access keys and known accounts granted write access"
+    ))]
+    /// Listing the function-call access keys and known accounts granted write access
+    List(self::list::List),
+    #[strum_discriminants(strum(
+        message = "check                -   Checking whether an access key or an account can write the given keys"
+    ))]
+    /// Checking whether an access key or an account can write the given keys
+    Check(self::check::Check),
 }
@@ -0,0 +1,33 @@
+//! The SocialDB contract has no method to revoke a write permission once it is granted, so the
+//! write access of a function-call access key is revoked by deleting the key from the account.
+//! The permissions granted to other accounts cannot be revoked.
+mod sign_as;
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = near_cli_rs::GlobalContext)]
+#[interactive_clap(output_context = RevokeWriteAccessContext)]
+pub struct RevokeWriteAccess {
+    /// Enter the public key of the function-call access key to revoke the write access from (the key is deleted):
+    public_key: near_cli_rs::types::public_key::PublicKey,
+    #[interactive_clap(named_arg)]
+    /// Specify signer account ID
+    sign_as: self::sign_as::Signer,
+}
+
+#[derive(Clone)]
+pub struct RevokeWriteAccessContext {
+    pub global_context: near_cli_rs::GlobalContext,
+    pub public_key: near_crypto::PublicKey,
+}
+
+impl RevokeWriteAccessContext {
+    pub fn from_previous_context(
+        previous_context: near_cli_rs::GlobalContext,
+        scope: &<RevokeWriteAccess as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        Ok(Self {
+            global_context: previous_context,
+            public_key: scope.public_key.0.clone(),
+        })
+    }
+}
@@ -0,0 +1,117 @@
+use color_eyre::eyre::WrapErr;
+use near_cli_rs::common::{JsonRpcClientExt, RpcQueryResponseExt};
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = super::RevokeWriteAccessContext)]
+#[interactive_clap(output_context = SignerContext)]
+pub struct Signer {
+    #[interactive_clap(skip_default_input_arg)]
+    /// What is the signer account ID?
+    signer_account_id: near_cli_rs::types::account_id::AccountId,
+    #[interactive_clap(named_arg)]
+    /// Select network
+    network_config: near_cli_rs::network_for_transaction::NetworkForTransactionArgs,
+}
+
+#[derive(Clone)]
+pub struct SignerContext {
+    global_context: near_cli_rs::GlobalContext,
+    public_key: near_crypto::PublicKey,
+    signer_account_id: near_primitives::types::AccountId,
+}
+
+impl SignerContext {
+    pub fn from_previous_context(
+        previous_context: super::RevokeWriteAccessContext,
+        scope: &<Signer as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        Ok(Self {
+            global_context: previous_context.global_context,
+            public_key: previous_context.public_key,
+            signer_account_id: scope.signer_account_id.clone().into(),
+        })
+    }
+}
+
+impl From<SignerContext> for near_cli_rs::commands::ActionContext {
+    fn from(item: SignerContext) -> Self {
+        let public_key = item.public_key.clone();
+        let signer_id = item.signer_account_id.clone();
+
+        let get_prepopulated_transaction_after_getting_network_callback: near_cli_rs::commands::GetPrepopulatedTransactionAfterGettingNetworkCallback = std::sync::Arc::new({
+            move |network_config| {
+                let near_social_account_id = &crate::common::get_near_social_account_id(network_config)?;
+                let access_key = network_config
+                    .json_rpc_client()
+                    .blocking_call_view_access_key(
+                        &signer_id,
+                        &public_key,
+                        near_primitives::types::Finality::Final.into(),
+                    )
+                    .wrap_err_with(|| format!("Failed to fetch the access key <{public_key}> of <{signer_id}>"))?
+                    .access_key_view()?;
+                // Deleting any other key would lock the user out of more than the SocialDB writes
+                if !near_socialdb_client::is_signer_access_key_function_call_access_can_call_set_on_social_db_account(
+                    near_social_account_id,
+                    &access_key.permission,
+                )
+                .unwrap_or(false)
+                {
+                    color_eyre::eyre::bail!(
+                        "The access key <{public_key}> of <{signer_id}> is not a function-call access key for <{near_social_account_id}>, so it is not deleted"
+                    );
+                }
+
+                Ok(near_cli_rs::commands::PrepopulatedTransaction {
+                    signer_id: signer_id.clone(),
+                    receiver_id: signer_id.clone(),
+                    actions: vec![near_primitives::transaction::Action::DeleteKey(Box::new(
+                        near_primitives::transaction::DeleteKeyAction {
+                            public_key: public_key.clone(),
+                        },
+                    ))],
+                })
+            }
+        });
+
+        let on_after_sending_transaction_callback: near_cli_rs::transaction_signature_options::OnAfterSendingTransactionCallback = std::sync::Arc::new({
+            let public_key = item.public_key.clone();
+            let signer_id = item.signer_account_id.clone();
+            move |transaction_info, _network_config| {
+                let near_primitives::views::FinalExecutionStatus::SuccessValue(_) = transaction_info.status else {
+                    color_eyre::eyre::bail!("Could not delete the access key <{public_key}> from <{signer_id}>");
+                };
+                eprintln!("<{signer_id}> has revoked the write access of public key <{public_key}> by deleting the key");
+                crate::output::emit(&serde_json::json!({
+                    "transaction_hash": transaction_info.transaction_outcome.id,
+                    "signer_account_id": signer_id,
+                    "revoked_from": { "public_key": public_key },
+                }))
+            }
+        });
+
+        Self {
+            global_context: item.global_context,
+            interacting_with_account_ids: vec![item.signer_account_id],
+            get_prepopulated_transaction_after_getting_network_callback,
+            on_before_signing_callback: std::sync::Arc::new(
+                |_prepolulated_unsinged_transaction, _network_config| Ok(()),
+            ),
+            on_before_sending_transaction_callback: std::sync::Arc::new(
+                |_signed_transaction, _network_config| Ok(String::new()),
+            ),
+            on_after_sending_transaction_callback,
+        }
+    }
+}
+
+impl Signer {
+    fn input_signer_account_id(
+        context: &super::RevokeWriteAccessContext,
+    ) -> color_eyre::eyre::Result<Option<near_cli_rs::types::account_id::AccountId>> {
+        near_cli_rs::common::input_signer_account_id_from_used_account_list(
+            &context.global_context.config.credentials_home_dir,
+            "What is the signer account ID?",
+        )
+    }
+}
@@ -0,0 +1,90 @@
+use color_eyre::eyre::{ContextCompat, WrapErr};
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = near_cli_rs::GlobalContext)]
+#[interactive_clap(output_context = ApplyContext)]
+pub struct Apply {
+    /// Enter the path to the profile file (JSON or YAML):
+    file: near_cli_rs::types::path_buf::PathBuf,
+    #[interactive_clap(skip_default_input_arg)]
+    /// Which account's profile do you want to replace?
+    account_id: near_cli_rs::types::account_id::AccountId,
+    #[interactive_clap(long)]
+    /// Skip the preview confirmation and sign the transaction right away
+    yes: bool,
+    #[interactive_clap(named_arg)]
+    /// Specify signer account ID
+    sign_as: crate::social_db::data::set::sign_as::Signer,
+}
+
+#[derive(Clone)]
+pub struct ApplyContext(crate::social_db::data::set::data::DataContext);
+
+impl ApplyContext {
+    pub fn from_previous_context(
+        previous_context: near_cli_rs::GlobalContext,
+        scope: &<Apply as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let profile = read_profile_file(&scope.file.0)?;
+        Ok(Self(crate::social_db::data::set::data::DataContext {
+            global_context: previous_context,
+            set_to_account_id: scope.account_id.clone(),
+            key: format!("{}/profile", scope.account_id),
+            get_value_after_getting_network_callback:
+                crate::social_db::data::set::data::fixed_value(profile),
+            skip_confirmation: scope.yes,
+            // The file is the whole profile, so the fields removed from it are removed from SocialDB
+            replace_existing: true,
+        }))
+    }
+}
+
+impl From<ApplyContext> for crate::social_db::data::set::data::DataContext {
+    fn from(item: ApplyContext) -> Self {
+        item.0
+    }
+}
+
+impl Apply {
+    pub fn input_account_id(
+        context: &near_cli_rs::GlobalContext,
+    ) -> color_eyre::eyre::Result<Option<near_cli_rs::types::account_id::AccountId>> {
+        near_cli_rs::common::input_signer_account_id_from_used_account_list(
+            &context.config.credentials_home_dir,
+            "Which account's profile do you want to replace?",
+        )
+    }
+}
+
+/// Reads the profile document (the content of `<account_id>/profile`), e.g.:
+///
+/// ```yaml
+/// name: Alice
+/// linktree:
+///   github: alice
+/// ```
+fn read_profile_file(path: &std::path::Path) -> color_eyre::eyre::Result<serde_json::Value> {
+    let file = std::fs::read_to_string(path)
+        .wrap_err_with(|| format!("Failed to read the profile file {}", path.display()))?;
+    let profile: serde_json::Value = match path.extension().and_then(|extension| extension.to_str())
+    {
+        Some("yaml" | "yml") => serde_yaml::from_str(&file)
+            .wrap_err_with(|| format!("The profile file {} is not valid YAML", path.display()))?,
+        _ => serde_json::from_str(&file)
+            .wrap_err_with(|| format!("The profile file {} is not valid JSON", path.display()))?,
+    };
+    profile
+        .as_object()
+        .wrap_err("The profile file must contain an object with the profile fields")?;
+    serde_json::from_value::<crate::socialdb_types::Profile>(profile.clone())
+        .wrap_err("The profile file does not match the profile format")?;
+    if let Some((path, _)) = crate::common::social_db_data_leaves(&profile)
+        .into_iter()
+        .find(|(_, value)| !value.is_string())
+    {
+        color_eyre::eyre::bail!(
+            "SocialDB only stores text values, but <{path}> in the profile file is not a string"
+        );
+    }
+    Ok(profile)
+}
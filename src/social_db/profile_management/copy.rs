@@ -0,0 +1,135 @@
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = near_cli_rs::GlobalContext)]
+#[interactive_clap(output_context = CopyProfileContext)]
+pub struct CopyProfile {
+    /// Which account's profile do you want to copy?
+    from_account_id: near_cli_rs::types::account_id::AccountId,
+    #[interactive_clap(skip_default_input_arg)]
+    /// Which account do you want to copy the profile to?
+    to_account_id: near_cli_rs::types::account_id::AccountId,
+    /// Rewrite the linktree entries, e.g. "website=example.com,twitter=" (an empty value removes the entry)
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    linktree: Option<near_cli_rs::types::vec_string::VecString>,
+    /// Replace the profile image with an image URL
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    image_url: Option<String>,
+    /// Replace the profile image with an IPFS CID
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    image_ipfs_cid: Option<String>,
+    /// Replace the profile background image with an image URL
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    background_image_url: Option<String>,
+    /// Replace the profile background image with an IPFS CID
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    background_image_ipfs_cid: Option<String>,
+    #[interactive_clap(long)]
+    /// Skip the preview confirmation and sign the transaction right away
+    yes: bool,
+    #[interactive_clap(named_arg)]
+    /// Specify signer account ID
+    sign_as: crate::social_db::data::set::sign_as::Signer,
+}
+
+#[derive(Clone)]
+pub struct CopyProfileContext(crate::social_db::data::set::data::DataContext);
+
+impl CopyProfileContext {
+    pub fn from_previous_context(
+        previous_context: near_cli_rs::GlobalContext,
+        scope: &<CopyProfile as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let linktree = scope
+            .linktree
+            .clone()
+            .map(Vec::<String>::from)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|entry| match entry.split_once('=') {
+                Some((name, value)) => Ok((name.trim().to_owned(), value.trim().to_owned())),
+                None => color_eyre::eyre::bail!(
+                    "The linktree entry <{entry}> must have the form <name>=<value>"
+                ),
+            })
+            .collect::<color_eyre::eyre::Result<Vec<_>>>()?;
+        let image = (scope.image_url.is_some() || scope.image_ipfs_cid.is_some()).then(|| {
+            crate::socialdb_types::ProfileImage {
+                url: scope.image_url.clone(),
+                ipfs_cid: scope.image_ipfs_cid.clone(),
+                ..Default::default()
+            }
+        });
+        let background_image = (scope.background_image_url.is_some()
+            || scope.background_image_ipfs_cid.is_some())
+        .then(|| crate::socialdb_types::ProfileImage {
+            url: scope.background_image_url.clone(),
+            ipfs_cid: scope.background_image_ipfs_cid.clone(),
+            ..Default::default()
+        });
+
+        let get_value_after_getting_network_callback: crate::social_db::data::set::data::GetValueAfterGettingNetworkCallback = std::sync::Arc::new({
+            let from_account_id: near_primitives::types::AccountId =
+                scope.from_account_id.clone().into();
+            move |network_config| {
+                let mut profile = super::view_profile::fetch_profile_snapshot(
+                    network_config,
+                    &from_account_id,
+                    &near_primitives::types::Finality::Final.into(),
+                    crate::cache::CachePolicy::Bypass,
+                )?
+                .profile;
+                if serde_json::to_value(&profile)? == serde_json::json!({}) {
+                    color_eyre::eyre::bail!("There is no profile for <{from_account_id}> to copy");
+                }
+
+                for (name, value) in &linktree {
+                    let profile_linktree = profile.linktree.get_or_insert_with(Default::default);
+                    if value.is_empty() {
+                        profile_linktree.remove(name);
+                    } else {
+                        profile_linktree.insert(name.clone(), Some(value.clone()));
+                    }
+                }
+                if image.is_some() {
+                    profile.image = image.clone();
+                }
+                if background_image.is_some() {
+                    profile.background_image = background_image.clone();
+                }
+                Ok(serde_json::to_value(profile)?)
+            }
+        });
+
+        Ok(Self(crate::social_db::data::set::data::DataContext {
+            global_context: previous_context,
+            set_to_account_id: scope.to_account_id.clone(),
+            key: format!("{}/profile", scope.to_account_id),
+            get_value_after_getting_network_callback,
+            skip_confirmation: scope.yes,
+            // The copy is the whole profile, so the fields the source profile does not have are
+            // removed from the target one
+            replace_existing: true,
+        }))
+    }
+}
+
+impl From<CopyProfileContext> for crate::social_db::data::set::data::DataContext {
+    fn from(item: CopyProfileContext) -> Self {
+        item.0
+    }
+}
+
+impl CopyProfile {
+    pub fn input_to_account_id(
+        context: &near_cli_rs::GlobalContext,
+    ) -> color_eyre::eyre::Result<Option<near_cli_rs::types::account_id::AccountId>> {
+        near_cli_rs::common::input_signer_account_id_from_used_account_list(
+            &context.config.credentials_home_dir,
+            "Which account do you want to copy the profile to?",
+        )
+    }
+}
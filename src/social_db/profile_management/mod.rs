@@ -1,5 +1,7 @@
 use strum::{EnumDiscriminants, EnumIter, EnumMessage};
 
+mod apply;
+mod copy;
 mod view_profile;
 
 #[derive(Debug, Clone, interactive_clap::InteractiveClap)]
@@ -20,4 +22,14 @@ pub enum Actions {
     #[strum_discriminants(strum(message = "update-profile  - Update profile for the account"))]
     /// Update profile for the account
     UpdateProfile(near_cli_rs::commands::account::update_social_profile::UpdateSocialProfile),
+    #[strum_discriminants(strum(
+        message = "apply           - Replace the profile of the account with a JSON/YAML file"
+    ))]
+    /// Replace the profile of the account with a JSON/YAML file
+    Apply(self::apply::Apply),
+    #[strum_discriminants(strum(
+        message = "copy            - Copy the profile of one account to another"
+    ))]
+    /// Copy the profile of one account to another
+    Copy(self::copy::CopyProfile),
 }
@@ -1,3 +1,5 @@
+use color_eyre::eyre::WrapErr;
+use near_cli_rs::common::{CallResultExt, JsonRpcClientExt};
 use strum::{EnumDiscriminants, EnumIter, EnumMessage};
 
 mod as_json;
@@ -55,3 +57,58 @@ pub enum OutputFormat {
     /// View account profile in the text
     AsText(self::as_text::AsText),
 }
+
+/// The profile of an account at a block.
+#[derive(Debug, serde::Serialize)]
+pub struct ProfileSnapshot {
+    pub account_id: near_primitives::types::AccountId,
+    pub block_height: near_primitives::types::BlockHeight,
+    pub block_hash: near_primitives::hash::CryptoHash,
+    pub profile: crate::socialdb_types::Profile,
+}
+
+/// Fetches `<account_id>/profile/**` at the block the account is viewed at, so the profile and
+/// the reported block are the same snapshot even for the `now` block reference.
+pub fn fetch_profile_snapshot(
+    network_config: &near_cli_rs::config::NetworkConfig,
+    account_id: &near_primitives::types::AccountId,
+    block_reference: &near_primitives::types::BlockReference,
+    cache_policy: crate::cache::CachePolicy,
+) -> color_eyre::eyre::Result<ProfileSnapshot> {
+    let near_social_account_id = &crate::common::get_near_social_account_id(network_config)?;
+
+    let rpc_query_response = network_config
+        .json_rpc_client()
+        .blocking_call_view_account(account_id, block_reference.clone())
+        .wrap_err_with(|| format!("Failed to fetch query ViewAccount for <{account_id}>"))?;
+
+    let mut profiles: std::collections::HashMap<
+        near_primitives::types::AccountId,
+        crate::socialdb_types::AccountProfile,
+    > = crate::common::call_view_function_with_failover(
+        network_config,
+        near_social_account_id,
+        "get",
+        serde_json::json!({
+            "keys": vec![format!("{account_id}/profile/**")],
+        })
+        .to_string()
+        .into_bytes(),
+        near_primitives::types::BlockId::Hash(rpc_query_response.block_hash).into(),
+        cache_policy,
+    )
+    .wrap_err_with(|| {
+        format!("Failed to fetch query for view method: 'get {account_id}/profile/**'")
+    })?
+    .parse_result_from_json()
+    .wrap_err_with(|| {
+        format!("Failed to parse view function call return value for {account_id}/profile.")
+    })?;
+
+    Ok(ProfileSnapshot {
+        account_id: account_id.clone(),
+        block_height: rpc_query_response.block_height,
+        block_hash: rpc_query_response.block_hash,
+        profile: profiles.remove(account_id).unwrap_or_default().profile,
+    })
+}
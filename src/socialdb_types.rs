@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 pub type ComponentName = String;
 
@@ -7,6 +7,14 @@ pub type KeyName = String;
 #[derive(Debug, Clone, serde::Serialize)]
 pub struct SocialDbQuery {
     pub keys: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub options: Option<SocialDbGetOptions>,
+}
+
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct SocialDbGetOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub with_block_height: Option<bool>,
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -74,3 +82,76 @@ pub struct SocialDbComponentMetadataImage {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub ipfs_cid: Option<String>,
 }
+
+/// The `<account_id>/profile/**` data of an account.
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct AccountProfile {
+    #[serde(default)]
+    pub profile: Profile,
+}
+
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct Profile {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub image: Option<ProfileImage>,
+    #[serde(rename = "backgroundImage", skip_serializing_if = "Option::is_none")]
+    pub background_image: Option<ProfileImage>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub linktree: Option<BTreeMap<String, Option<String>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tags: Option<BTreeMap<String, Option<String>>>,
+    /// Any other profile keys, kept as they are stored in SocialDB.
+    #[serde(flatten)]
+    pub other: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct ProfileImage {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ipfs_cid: Option<String>,
+    /// Other image sources (e.g. `nft`).
+    #[serde(flatten)]
+    pub other: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct SocialDbKeysQuery {
+    pub keys: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub options: Option<SocialDbKeysOptions>,
+}
+
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct SocialDbKeysOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub return_type: Option<SocialDbKeysReturnType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub values_only: Option<bool>,
+}
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    serde::Serialize,
+    strum_macros::Display,
+    strum_macros::EnumString,
+)]
+#[strum(ascii_case_insensitive)]
+pub enum SocialDbKeysReturnType {
+    True,
+    BlockHeight,
+    NodeId,
+}
+
+impl interactive_clap::ToCli for SocialDbKeysReturnType {
+    type CliVariant = SocialDbKeysReturnType;
+}
@@ -0,0 +1,24 @@
+use strum::{EnumDiscriminants, EnumIter, EnumMessage};
+
+mod send_signed;
+pub mod unsigned;
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(context = near_cli_rs::GlobalContext)]
+pub struct Transaction {
+    #[interactive_clap(subcommand)]
+    transaction_actions: TransactionActions,
+}
+
+#[derive(Debug, EnumDiscriminants, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(context = near_cli_rs::GlobalContext)]
+#[strum_discriminants(derive(EnumMessage, EnumIter))]
+#[non_exhaustive]
+/// What do you want to do with a transaction?
+pub enum TransactionActions {
+    #[strum_discriminants(strum(
+        message = "send-signed   -  Broadcast an exported transaction signed offline"
+    ))]
+    /// Broadcast an exported transaction signed offline
+    SendSigned(self::send_signed::SendSigned),
+}
@@ -0,0 +1,85 @@
+use std::str::FromStr;
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = near_cli_rs::GlobalContext)]
+#[interactive_clap(output_context = SendSignedContext)]
+pub struct SendSigned {
+    /// What is the path to the exported unsigned transaction file?
+    file: near_cli_rs::types::path_buf::PathBuf,
+    /// The transaction signed offline (base64-encoded)
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    signed_transaction: Option<near_cli_rs::types::signed_transaction::SignedTransactionAsBase64>,
+    /// The signature of the transaction hash made offline (e.g. ed25519:...)
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    signature: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SendSignedContext;
+
+impl SendSignedContext {
+    pub fn from_previous_context(
+        previous_context: near_cli_rs::GlobalContext,
+        scope: &<SendSigned as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let file = super::unsigned::UnsignedTransactionFile::read(&scope.file.0)?;
+        let network_config = &file.network_config(&previous_context.config)?;
+        let unsigned_transaction = file.transaction()?;
+        let (transaction_hash, _) = unsigned_transaction.get_hash_and_size();
+
+        let signed_transaction = match (&scope.signed_transaction, &scope.signature) {
+            (Some(_), Some(_)) => {
+                color_eyre::eyre::bail!("Pass either --signed-transaction or --signature, not both")
+            }
+            (Some(signed_transaction), None) => {
+                let signed_transaction = signed_transaction.inner.clone();
+                if signed_transaction.get_hash() != transaction_hash {
+                    color_eyre::eyre::bail!(
+                        "The signed transaction <{}> is not the exported transaction <{transaction_hash}>",
+                        signed_transaction.get_hash()
+                    );
+                }
+                signed_transaction
+            }
+            (None, signature) => {
+                let signature = match signature {
+                    Some(signature) => signature.clone(),
+                    None => inquire::Text::new(&format!(
+                        "What is the signature of the transaction hash <{transaction_hash}>?"
+                    ))
+                    .prompt()?,
+                };
+                let signature = near_crypto::Signature::from_str(signature.trim())
+                    .map_err(|err| color_eyre::eyre::eyre!("Invalid signature: {err}"))?;
+                near_primitives::transaction::SignedTransaction::new(
+                    signature,
+                    unsigned_transaction,
+                )
+            }
+        };
+        if !signed_transaction.signature.verify(
+            transaction_hash.as_ref(),
+            signed_transaction.transaction.public_key(),
+        ) {
+            color_eyre::eyre::bail!(
+                "The signature does not match the key <{}> of the exported transaction",
+                signed_transaction.transaction.public_key()
+            );
+        }
+
+        let transaction_info =
+            near_cli_rs::transaction_signature_options::send::sending_signed_transaction(
+                network_config,
+                &signed_transaction,
+            )?;
+        near_cli_rs::common::print_transaction_status(
+            &transaction_info,
+            network_config,
+            &previous_context.verbosity,
+        )?;
+        file.operation.print_status(&transaction_info)?;
+        Ok(Self)
+    }
+}
@@ -0,0 +1,203 @@
+//! Unsigned transactions exported for signing on another (e.g. air-gapped) machine.
+use std::str::FromStr;
+
+use color_eyre::eyre::{ContextCompat, WrapErr};
+use near_cli_rs::common::{JsonRpcClientExt, RpcQueryResponseExt};
+
+/// The file written by `--export-unsigned-transaction` and read by `bos transaction send-signed`.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct UnsignedTransactionFile {
+    pub network_name: String,
+    /// The near CLI network connection the transaction was exported on (it is missing in the
+    /// files exported before it was added).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub connection_name: Option<String>,
+    pub operation: Operation,
+    /// The hash that has to be signed.
+    pub transaction_hash: String,
+    /// The transaction in the base64 format of `near transaction sign-transaction`.
+    pub unsigned_transaction: String,
+}
+
+/// The command that prepared the transaction, so its result is reported once it is sent.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+pub enum Operation {
+    ComponentsDeploy {
+        deploy_to_account_id: near_primitives::types::AccountId,
+        social_db_folder: String,
+    },
+    DataSet {
+        set_to_account_id: near_primitives::types::AccountId,
+    },
+}
+
+impl Operation {
+    pub fn print_status(
+        &self,
+        transaction_info: &near_primitives::views::FinalExecutionOutcomeView,
+    ) -> color_eyre::eyre::Result<()> {
+        crate::cache::invalidate_final_block_entries();
+        match self {
+            Self::ComponentsDeploy {
+                deploy_to_account_id,
+                social_db_folder,
+            } => crate::common::print_deployed_components(
+                transaction_info,
+                deploy_to_account_id,
+                social_db_folder,
+            ),
+            Self::DataSet { set_to_account_id } => {
+                crate::common::print_data_set_status(transaction_info, set_to_account_id)
+            }
+        }
+    }
+}
+
+impl UnsignedTransactionFile {
+    pub fn read(path: &std::path::Path) -> color_eyre::eyre::Result<Self> {
+        let file = std::fs::read(path)
+            .wrap_err_with(|| format!("Failed to read the file {}", path.display()))?;
+        serde_json::from_slice(&file).wrap_err_with(|| {
+            format!(
+                "The file {} is not an unsigned transaction exported by bos",
+                path.display()
+            )
+        })
+    }
+
+    /// The network connection to send the transaction with: the one it was exported on, or the
+    /// one with the same `network_name` (the user selects it when there are several).
+    pub fn network_config(
+        &self,
+        config: &near_cli_rs::config::Config,
+    ) -> color_eyre::eyre::Result<near_cli_rs::config::NetworkConfig> {
+        if let Some(connection_name) = &self.connection_name {
+            return config
+                .network_connection
+                .get(connection_name)
+                .cloned()
+                .wrap_err_with(|| {
+                    format!(
+                        "The network connection <{connection_name}> of the exported transaction is not configured"
+                    )
+                });
+        }
+        let connection_names = config
+            .network_connection
+            .iter()
+            .filter(|(_, network_config)| network_config.network_name == self.network_name)
+            .map(|(connection_name, _)| connection_name.clone())
+            .collect::<Vec<_>>();
+        let connection_name = match connection_names.as_slice() {
+            [] => color_eyre::eyre::bail!(
+                "There is no network connection to the <{}> network of the exported transaction",
+                self.network_name
+            ),
+            [connection_name] => connection_name.clone(),
+            _ => inquire::Select::new(
+                &format!(
+                    "Which network connection should send the transaction to the <{}> network?",
+                    self.network_name
+                ),
+                connection_names,
+            )
+            .prompt()?,
+        };
+        Ok(config.network_connection[&connection_name].clone())
+    }
+
+    pub fn transaction(
+        &self,
+    ) -> color_eyre::eyre::Result<near_primitives::transaction::Transaction> {
+        let transaction = near_cli_rs::types::transaction::TransactionAsBase64::from_str(
+            &self.unsigned_transaction,
+        )
+        .map_err(|err| color_eyre::eyre::eyre!("Invalid unsigned transaction: {err}"))?;
+        Ok(transaction.into())
+    }
+}
+
+/// Completes the prepopulated transaction with the current nonce and block hash of the signer key,
+/// runs the `on_before_signing_callback` (e.g. to compute the storage deposit) and writes it to
+/// the file instead of signing it.
+pub fn export(
+    path: &std::path::Path,
+    config: &near_cli_rs::config::Config,
+    network_config: &near_cli_rs::config::NetworkConfig,
+    prepopulated_transaction: &near_cli_rs::commands::PrepopulatedTransaction,
+    signer_public_key: &near_crypto::PublicKey,
+    on_before_signing_callback: &near_cli_rs::commands::OnBeforeSigningCallback,
+    operation: Operation,
+) -> color_eyre::eyre::Result<()> {
+    let rpc_query_response = network_config
+        .json_rpc_client()
+        .blocking_call_view_access_key(
+            &prepopulated_transaction.signer_id,
+            signer_public_key,
+            near_primitives::types::BlockReference::latest(),
+        )
+        .wrap_err_with(|| {
+            format!(
+                "Failed to fetch the access key <{signer_public_key}> of <{}> on network <{}>",
+                prepopulated_transaction.signer_id, network_config.network_name
+            )
+        })?;
+
+    let mut unsigned_transaction = near_primitives::transaction::TransactionV0 {
+        public_key: signer_public_key.clone(),
+        block_hash: rpc_query_response.block_hash,
+        nonce: rpc_query_response.access_key_view()?.nonce + 1,
+        signer_id: prepopulated_transaction.signer_id.clone(),
+        receiver_id: prepopulated_transaction.receiver_id.clone(),
+        actions: prepopulated_transaction.actions.clone(),
+    };
+    on_before_signing_callback(&mut unsigned_transaction, network_config)?;
+
+    let transaction_hash =
+        near_primitives::transaction::Transaction::V0(unsigned_transaction.clone())
+            .get_hash_and_size()
+            .0;
+    let unsigned_transaction =
+        near_cli_rs::types::transaction::TransactionAsBase64::from(unsigned_transaction)
+            .to_string();
+    // near CLI only passes the selected network config, so find the connection it comes from
+    let network_config_value = serde_json::to_value(network_config)?;
+    let connection_name = config
+        .network_connection
+        .iter()
+        .find(|(_, candidate)| {
+            serde_json::to_value(candidate).is_ok_and(|value| value == network_config_value)
+        })
+        .map(|(connection_name, _)| connection_name.clone());
+    let file = UnsignedTransactionFile {
+        network_name: network_config.network_name.clone(),
+        connection_name,
+        operation,
+        transaction_hash: transaction_hash.to_string(),
+        unsigned_transaction: unsigned_transaction.clone(),
+    };
+    std::fs::write(path, serde_json::to_string_pretty(&file)?)
+        .wrap_err_with(|| format!("Failed to write the file {}", path.display()))?;
+
+    crate::human_println!(
+        "\nThe unsigned transaction <{transaction_hash}> was exported to {}",
+        path.display()
+    );
+    crate::human_println!("Sign it on the machine with the <{signer_public_key}> key, e.g. with:");
+    crate::human_println!(
+        " near transaction sign-transaction {unsigned_transaction} network-config {} ...",
+        file.connection_name
+            .as_deref()
+            .unwrap_or(&network_config.network_name)
+    );
+    crate::human_println!("and broadcast it within a day (before its block hash expires) with:");
+    crate::human_println!(
+        " bos transaction send-signed {} --signed-transaction <SIGNED_TRANSACTION>",
+        path.display()
+    );
+    crate::output::emit(&serde_json::json!({
+        "unsigned_transaction_file": path,
+        "transaction_hash": transaction_hash,
+    }))
+}
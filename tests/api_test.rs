@@ -0,0 +1,73 @@
+mod social_db_emulator;
+use social_db_emulator::SocialDbEmulator;
+
+#[test]
+fn test_api_plan_deploy_against_remote_components() {
+    let emulator = SocialDbEmulator::start();
+    emulator.set(serde_json::json!({
+        "test.near": {
+            "widget": {
+                "Unchanged": { "": "return <div>Same</div>;" },
+                "Updated": { "": "return <div>Old</div>;" }
+            }
+        }
+    }));
+
+    let src_dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        src_dir.path().join("Unchanged.jsx"),
+        "return <div>Same</div>;\n",
+    )
+    .unwrap();
+    std::fs::write(src_dir.path().join("Updated.jsx"), "return <div>New</div>;").unwrap();
+    std::fs::create_dir(src_dir.path().join("Nested")).unwrap();
+    std::fs::write(
+        src_dir.path().join("Nested").join("New.jsx"),
+        "return <div>New</div>;",
+    )
+    .unwrap();
+
+    let local_components = bos_cli::api::load_local_components(src_dir.path()).unwrap();
+    let mut component_names = local_components.keys().cloned().collect::<Vec<_>>();
+    component_names.sort();
+    assert_eq!(component_names, ["Nested.New", "Unchanged", "Updated"]);
+
+    let json_rpc_client = near_jsonrpc_client::JsonRpcClient::connect(emulator.url());
+    let remote_components = tokio::runtime::Runtime::new()
+        .unwrap()
+        .block_on(bos_cli::api::fetch_remote_components(
+            &bos_cli::rpc::RpcEndpoints::new(vec![json_rpc_client]),
+            &"social.near".parse().unwrap(),
+            &"test.near".parse().unwrap(),
+            "widget",
+            &component_names,
+            bos_cli::api::FetchOptions {
+                chunk_size: 1,
+                concurrency: 2,
+                ..Default::default()
+            },
+            bos_cli::cache::CachePolicy::Bypass,
+        ))
+        .unwrap();
+    assert_eq!(remote_components.len(), 2);
+
+    let plan = bos_cli::api::plan_deploy(local_components, &remote_components);
+    assert!(!plan.is_empty());
+    assert!(plan.new.contains_key("Nested.New"));
+    assert!(plan.updated.contains_key("Updated"));
+    assert!(plan.unchanged.contains_key("Unchanged"));
+    assert_eq!(plan.into_components_to_deploy().len(), 2);
+}
+
+#[test]
+fn test_block_on_inside_async_context() {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let result = runtime.block_on(async { bos_cli::common::block_on(async { 42 }) });
+    assert_eq!(result, 42);
+    assert_eq!(bos_cli::common::block_on(async { 42 }), 42);
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn test_block_on_inside_current_thread_async_context() {
+    assert_eq!(bos_cli::common::block_on(async { 42 }), 42);
+}
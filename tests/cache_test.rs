@@ -0,0 +1,80 @@
+mod test_util;
+use httpmock::prelude::*;
+use serde_json::json;
+use test_util::*;
+
+#[test]
+fn test_bos_socialdb_data_view_uses_response_cache() {
+    let server = MockServer::start();
+    let get_mock = server.mock(|when, then| {
+        when.method(POST)
+            .path("/")
+            .body_contains(r#""request_type":"call_function""#)
+            .body_contains(r#""method_name":"get""#);
+        let result: Vec<u8> = json!({ "test.near": { "profile": { "name": "Alice" } } })
+            .to_string()
+            .into_bytes();
+        then.status(200).json_body(json!({
+            "jsonrpc": "2.0",
+            "result": {
+                "result": result,
+                "logs": [],
+                "block_height": 17817336,
+                "block_hash": "4qkA4sUUG8opjH5Q9bL5mWJTnfR4ech879Db1BZXbx6P"
+            },
+            "id": "dontcare"
+        }));
+    });
+
+    let config_dir = dirs::config_dir().unwrap().join("near-cli");
+    let backup_path = setup_config(&config_dir, &server.url("/"));
+
+    let bos = |args: &[&str]| bos_command().args(args).assert();
+    let view = |global_args: &[&str]| {
+        bos(&[
+            global_args,
+            &[
+                "social-db",
+                "data",
+                "view",
+                "test.near/profile/**",
+                "--format",
+                "flat",
+                "network-config",
+                "mainnet",
+                "now",
+            ],
+        ]
+        .concat())
+        .success()
+        .stdout("test.near/profile/name = \"Alice\"\n");
+    };
+
+    view(&[]);
+    view(&[]);
+    get_mock.assert_hits(1);
+
+    // The expired entries are pruned when the cache is used
+    let expired_entry_path = test_cache_dir().join("expired.json");
+    std::fs::write(
+        &expired_entry_path,
+        json!({ "created_at": 0, "expires": true, "result": "" }).to_string(),
+    )
+    .unwrap();
+    view(&[]);
+    get_mock.assert_hits(1);
+    assert!(!expired_entry_path.exists());
+
+    view(&["--no-cache"]);
+    get_mock.assert_hits(2);
+
+    bos(&["extensions", "clear-cache"])
+        .success()
+        .stderr(predicates::str::contains(
+            "Removed 1 cached SocialDB responses",
+        ));
+    view(&[]);
+    get_mock.assert_hits(3);
+
+    restore_config(&config_dir, backup_path);
+}
@@ -0,0 +1,88 @@
+mod social_db_emulator;
+mod test_util;
+use base64::prelude::*;
+use social_db_emulator::SocialDbEmulator;
+use test_util::*;
+
+#[test]
+fn test_bos_components_deploy_propose_as_adds_sputnik_dao_proposal() {
+    let proposal_bond = 100_000_000_000_000_000_000_000;
+    let emulator = SocialDbEmulator::start();
+    emulator.add_dao("widgets.sputnik-dao.near", proposal_bond);
+    emulator.set(serde_json::json!({
+        "widgets.sputnik-dao.near": {
+            "widget": { "Header": { "": "return <h1>Hello</h1>;" } }
+        }
+    }));
+
+    let config_dir = dirs::config_dir().unwrap().join("near-cli");
+    let backup_path = setup_config(&config_dir, &emulator.url());
+    let temp_dir = setup_temp_dir();
+    std::fs::write(
+        temp_dir.path().join("src").join("Header.jsx"),
+        "return <h1>Hello, DAO</h1>;",
+    )
+    .unwrap();
+    std::fs::write(
+        temp_dir.path().join("src").join("Footer.jsx"),
+        "return <footer />;",
+    )
+    .unwrap();
+
+    bos_command()
+        .current_dir(temp_dir.path())
+        .args([
+            "components",
+            "deploy",
+            "widgets.sputnik-dao.near",
+            "propose-as",
+            "test.near",
+            "--description",
+            "Release 1.2",
+            "network-config",
+            "mainnet",
+            "sign-with-plaintext-private-key",
+            "--signer-public-key",
+            "ed25519:7fvCiaE4NTmhexo8fDoa3CFNupL6mvJmNjL1hydN65fm",
+            "--signer-private-key",
+            "ed25519:VzeoRptTNGWeXwq3JNLdo8XqoBKvjSXyV5VxjvxPyzsiGmwo2Vu6LTiBujoQSXYjF8khQS5r3SSQK3xV8uomjv7",
+            "send",
+        ])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains(
+            "Proposal #0 was added to <widgets.sputnik-dao.near>.",
+        ));
+
+    // The components are only deployed once the proposal is approved
+    assert_eq!(
+        emulator.get(&["widgets.sputnik-dao.near/widget/Header"]),
+        serde_json::json!({
+            "widgets.sputnik-dao.near": { "widget": { "Header": "return <h1>Hello</h1>;" } }
+        })
+    );
+
+    let proposals = emulator.proposals("widgets.sputnik-dao.near");
+    assert_eq!(proposals.len(), 1);
+    assert_eq!(
+        proposals[0]["description"],
+        "Release 1.2\n\nDeploy 2 components to widgets.sputnik-dao.near/widget/ on social.near:\n * Footer (new)\n * Header (updated: +1 -1 lines)"
+    );
+    let function_call = &proposals[0]["kind"]["FunctionCall"];
+    assert_eq!(function_call["receiver_id"], "social.near");
+    let action = &function_call["actions"][0];
+    assert_eq!(action["method_name"], "set");
+    assert_ne!(action["deposit"], "0");
+    let set_args: serde_json::Value = serde_json::from_slice(
+        &BASE64_STANDARD
+            .decode(action["args"].as_str().unwrap())
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        set_args["data"]["widgets.sputnik-dao.near"]["widget"]["Footer"][""],
+        "return <footer />;"
+    );
+
+    restore_config(&config_dir, backup_path);
+}
@@ -0,0 +1,62 @@
+mod social_db_emulator;
+mod test_util;
+use social_db_emulator::SocialDbEmulator;
+use test_util::*;
+
+#[test]
+fn test_bos_socialdb_data_delete_multiple_patterns() {
+    let emulator = SocialDbEmulator::start();
+    emulator.storage_deposit("test.near", 100_000_000_000_000_000_000_000);
+    emulator.set(serde_json::json!({
+        "test.near": {
+            "post": { "main": "hello" },
+            "profile": { "name": "Alice", "description": "Kept" },
+        }
+    }));
+
+    let config_dir = dirs::config_dir().unwrap().join("near-cli");
+    let backup_path = setup_config(&config_dir, &emulator.url());
+
+    let delete = |extra_args: &[&str]| {
+        bos_command()
+            .args(["social-db", "data", "delete", "test.near/post/**,test.near/profile/name"])
+            .args(extra_args)
+            .args([
+                "sign-as",
+                "test.near",
+                "network-config",
+                "mainnet",
+                "sign-with-plaintext-private-key",
+                "--signer-public-key",
+                "ed25519:7fvCiaE4NTmhexo8fDoa3CFNupL6mvJmNjL1hydN65fm",
+                "--signer-private-key",
+                "ed25519:VzeoRptTNGWeXwq3JNLdo8XqoBKvjSXyV5VxjvxPyzsiGmwo2Vu6LTiBujoQSXYjF8khQS5r3SSQK3xV8uomjv7",
+                "send",
+            ])
+            .assert()
+            .success()
+    };
+
+    // The preview covers the keys matched by both patterns, including the key and node overhead
+    delete(&["--dry-run"])
+        .stdout(predicates::str::contains(
+            "2 key(s) will be deleted, releasing an estimated 810 bytes",
+        ))
+        .stdout(predicates::str::contains("Dry run: nothing was deleted."));
+    assert!(emulator.transactions().is_empty());
+
+    delete(&[]).stdout(predicates::str::contains(
+        "Keys successfully removed from <test.near>",
+    ));
+    assert_eq!(emulator.transactions().len(), 1);
+    assert_eq!(
+        emulator.get(&["test.near/**"]),
+        serde_json::json!({
+            "test.near": {
+                "profile": { "description": "Kept" },
+            }
+        })
+    );
+
+    restore_config(&config_dir, backup_path);
+}
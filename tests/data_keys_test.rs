@@ -0,0 +1,35 @@
+mod social_db_emulator;
+mod test_util;
+use social_db_emulator::SocialDbEmulator;
+use test_util::*;
+
+#[test]
+fn test_bos_socialdb_data_keys_output_formats() {
+    let emulator = SocialDbEmulator::start();
+    emulator.set(serde_json::json!({
+        "test.near": {
+            "profile": { "name": "Alice", "linktree": { "github": "alice" } },
+        }
+    }));
+
+    let config_dir = dirs::config_dir().unwrap().join("near-cli");
+    let backup_path = setup_config(&config_dir, &emulator.url());
+
+    let keys = |format_args: &[&str]| {
+        bos_command()
+            .args(["social-db", "data", "keys", "test.near/profile/**"])
+            .args(format_args)
+            .args(["network-config", "mainnet", "now"])
+            .assert()
+            .success()
+    };
+
+    keys(&[])
+        .stdout("test.near\n└── profile\n    ├── linktree\n    │   └── github\n    └── name\n");
+    keys(&["--format", "flat"])
+        .stdout("test.near/profile/linktree/github = true\ntest.near/profile/name = true\n");
+    keys(&["--format", "compact-json"])
+        .stdout("{\"test.near\":{\"profile\":{\"linktree\":{\"github\":true},\"name\":true}}}\n");
+
+    restore_config(&config_dir, backup_path);
+}
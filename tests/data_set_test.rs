@@ -0,0 +1,68 @@
+mod test_util;
+use base64::prelude::*;
+use httpmock::prelude::HttpMockRequest;
+use test_util::*;
+
+fn match_broadcast_tx_commit_for_profile_name(req: &HttpMockRequest) -> bool {
+    let Some(body) = &req.body else {
+        return false;
+    };
+    let Ok(json_body) = serde_json::from_slice::<serde_json::Value>(body) else {
+        return false;
+    };
+    json_body
+        .get("params")
+        .and_then(|params| params.get(0))
+        .and_then(|params| params.as_str())
+        .and_then(|params| BASE64_STANDARD.decode(params).ok())
+        .map(|decoded_params| {
+            String::from_utf8_lossy(&decoded_params)
+                .contains(r#"{"data":{"test.near":{"profile":{"name":"Alice"}}}}"#)
+        })
+        .unwrap_or(false)
+}
+
+#[test]
+fn test_bos_socialdb_data_set_with_mocked_rpc() {
+    // Start a mock server to simulate the NEAR RPC server
+    let mut server = setup_mock_server();
+    server = mock_broadcast_tx_commit(server, "", match_broadcast_tx_commit_for_profile_name);
+    server = mock_unmatched(server);
+
+    let config_dir = dirs::config_dir().unwrap().join("near-cli");
+    let backup_path = setup_config(&config_dir, &server.url("/"));
+
+    let mut cmd = bos_command();
+
+    // `--yes` skips the interactive confirmation, so the preview is printed and the transaction is sent
+    cmd.args([
+        "social-db",
+        "data",
+        "set",
+        "test.near/profile/name",
+        "--yes",
+        "with-text",
+        "Alice",
+        "sign-as",
+        "test.near",
+        "network-config",
+        "mainnet",
+        "sign-with-plaintext-private-key",
+        "--signer-public-key",
+        "ed25519:7fvCiaE4NTmhexo8fDoa3CFNupL6mvJmNjL1hydN65fm",
+        "--signer-private-key",
+        "ed25519:VzeoRptTNGWeXwq3JNLdo8XqoBKvjSXyV5VxjvxPyzsiGmwo2Vu6LTiBujoQSXYjF8khQS5r3SSQK3xV8uomjv7",
+        "send",
+    ])
+    .assert()
+    .success()
+    .stdout(predicates::str::contains(
+        r#""test.near/profile/name": "Alice""#,
+    ))
+    .stdout(predicates::str::contains("required_deposit"))
+    .stdout(predicates::str::contains(
+        "Keys successfully installed on <test.near>",
+    ));
+
+    restore_config(&config_dir, backup_path);
+}
@@ -0,0 +1,76 @@
+mod test_util;
+use httpmock::prelude::*;
+use serde_json::json;
+use test_util::*;
+
+fn setup_mock_server_with_profile() -> MockServer {
+    let server = MockServer::start();
+
+    // Mock the `query` RPC call for `get` returning a small profile
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/")
+            .body_contains(r#""request_type":"call_function""#)
+            .body_contains(r#""method_name":"get""#);
+        let result: Vec<u8> = json!({
+            "test.near": {
+                "profile": {
+                    "name": "Alice",
+                    "linktree": {
+                        "github": "alice"
+                    }
+                }
+            }
+        })
+        .to_string()
+        .into_bytes();
+        then.status(200).json_body(json!({
+            "jsonrpc": "2.0",
+            "result": {
+                "result": result,
+                "logs": [],
+                "block_height": 17817336,
+                "block_hash": "4qkA4sUUG8opjH5Q9bL5mWJTnfR4ech879Db1BZXbx6P"
+            },
+            "id": "dontcare"
+        }));
+    });
+
+    server
+}
+
+#[test]
+fn test_bos_socialdb_data_view_output_formats() {
+    let server = mock_unmatched(setup_mock_server_with_profile());
+
+    let config_dir = dirs::config_dir().unwrap().join("near-cli");
+    let backup_path = setup_config(&config_dir, &server.url("/"));
+
+    let view = |format: &str| {
+        bos_command()
+            .args([
+                "social-db",
+                "data",
+                "view",
+                "test.near/profile/**",
+                "--format",
+                format,
+                "network-config",
+                "mainnet",
+                "now",
+            ])
+            .assert()
+    };
+
+    view("flat").success().stdout(
+        "test.near/profile/linktree/github = \"alice\"\ntest.near/profile/name = \"Alice\"\n",
+    );
+    view("compact-json").success().stdout(
+        "{\"test.near\":{\"profile\":{\"linktree\":{\"github\":\"alice\"},\"name\":\"Alice\"}}}\n",
+    );
+    view("raw").failure().stderr(predicates::str::contains(
+        "The raw output format requires the key to resolve to a single value, but 2 values were found",
+    ));
+
+    restore_config(&config_dir, backup_path);
+}
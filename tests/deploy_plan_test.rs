@@ -0,0 +1,109 @@
+mod social_db_emulator;
+mod test_util;
+use social_db_emulator::SocialDbEmulator;
+use test_util::*;
+
+#[test]
+fn test_bos_components_plan_and_apply() {
+    let emulator = SocialDbEmulator::start();
+    emulator.set(serde_json::json!({
+        "test.near": { "widget": { "Header": { "": "return <h1>Hello</h1>;" } } }
+    }));
+
+    let config_dir = dirs::config_dir().unwrap().join("near-cli");
+    let backup_path = setup_config(&config_dir, &emulator.url());
+    let temp_dir = setup_temp_dir();
+    std::fs::write(
+        temp_dir.path().join("src").join("Header.jsx"),
+        "return <h1>Hello, world</h1>;",
+    )
+    .unwrap();
+    std::fs::write(
+        temp_dir.path().join("src").join("Footer.jsx"),
+        "return <footer />;",
+    )
+    .unwrap();
+
+    let bos = |args: &[&str]| {
+        bos_command()
+            .current_dir(temp_dir.path())
+            .args(args)
+            .assert()
+    };
+    let plan = || {
+        bos(&[
+            "components",
+            "plan",
+            "test.near",
+            "network-config",
+            "mainnet",
+        ])
+        .success()
+        .stdout(predicates::str::contains(
+            "The plan to deploy <2> components to <test.near>/widget/",
+        ));
+        std::fs::read_to_string(temp_dir.path().join("deploy-plan.json")).unwrap()
+    };
+    let apply = || {
+        bos(&[
+            "components",
+            "apply",
+            "deploy-plan.json",
+            "sign-as",
+            "test.near",
+            "network-config",
+            "mainnet",
+            "sign-with-plaintext-private-key",
+            "--signer-public-key",
+            "ed25519:7fvCiaE4NTmhexo8fDoa3CFNupL6mvJmNjL1hydN65fm",
+            "--signer-private-key",
+            "ed25519:VzeoRptTNGWeXwq3JNLdo8XqoBKvjSXyV5VxjvxPyzsiGmwo2Vu6LTiBujoQSXYjF8khQS5r3SSQK3xV8uomjv7",
+            "send",
+        ])
+    };
+
+    let plan_file = plan();
+    assert_eq!(
+        plan(),
+        plan_file,
+        "The plan is expected to be deterministic"
+    );
+    let plan_json: serde_json::Value = serde_json::from_str(&plan_file).unwrap();
+    assert_eq!(plan_json["network"], "mainnet");
+    assert_eq!(plan_json["components"]["Footer"]["change"], "new");
+    assert_eq!(plan_json["components"]["Header"]["change"], "updated");
+    assert!(plan_json["components"]["Header"]["diff"]
+        .as_str()
+        .unwrap()
+        .contains("+return <h1>Hello, world</h1>;"));
+
+    // The deployed component changes after the plan is made, so the plan is rejected
+    emulator.set(serde_json::json!({
+        "test.near": { "widget": { "Header": { "": "return <h1>Hi</h1>;" } } }
+    }));
+    apply()
+        .failure()
+        .stderr(predicates::str::contains(
+            "The deployed components have changed since the plan was made",
+        ))
+        .stderr(predicates::str::contains(": Header. Make a new plan."));
+    assert!(emulator.transactions().is_empty());
+
+    plan();
+    apply().success().stdout(predicates::str::contains(
+        "<2> components were successfully deployed to <test.near>/widget/",
+    ));
+    assert_eq!(
+        emulator.get(&["test.near/widget/*"]),
+        serde_json::json!({
+            "test.near": {
+                "widget": {
+                    "Footer": "return <footer />;",
+                    "Header": "return <h1>Hello, world</h1>;"
+                }
+            }
+        })
+    );
+
+    restore_config(&config_dir, backup_path);
+}
@@ -0,0 +1,64 @@
+use base64::Engine;
+use httpmock::prelude::*;
+use serde_json::json;
+
+#[test]
+fn test_fetch_remote_components_retries_and_reports_failed_components() {
+    let server = MockServer::start();
+
+    // Only the `Good` component can be fetched on its own, everything else is rate limited
+    let good_component_args = base64::engine::general_purpose::STANDARD
+        .encode(json!({ "keys": ["test.near/widget/Good/**"] }).to_string());
+    let good_component_mock = server.mock(|when, then| {
+        when.method(POST)
+            .path("/")
+            .body_contains(format!(r#""args_base64":"{good_component_args}""#));
+        let result: Vec<u8> = json!({
+            "test.near": { "widget": { "Good": { "": "return <div>Good</div>;" } } }
+        })
+        .to_string()
+        .into_bytes();
+        then.status(200).json_body(json!({
+            "jsonrpc": "2.0",
+            "result": {
+                "result": result,
+                "logs": [],
+                "block_height": 17817336,
+                "block_hash": "4qkA4sUUG8opjH5Q9bL5mWJTnfR4ech879Db1BZXbx6P"
+            },
+            "id": "dontcare"
+        }));
+    });
+    let rate_limited_mock = server.mock(|when, then| {
+        when.method(POST).path("/");
+        then.status(429);
+    });
+
+    let json_rpc_client = near_jsonrpc_client::JsonRpcClient::connect(server.url("/"));
+    let err = tokio::runtime::Runtime::new()
+        .unwrap()
+        .block_on(bos_cli::api::fetch_remote_components(
+            &bos_cli::rpc::RpcEndpoints::new(vec![json_rpc_client]),
+            &"social.near".parse().unwrap(),
+            &"test.near".parse().unwrap(),
+            "widget",
+            &["Good".to_string(), "Bad".to_string()],
+            bos_cli::api::FetchOptions {
+                max_retries: 2,
+                initial_backoff: std::time::Duration::from_millis(1),
+                ..Default::default()
+            },
+            bos_cli::cache::CachePolicy::Bypass,
+        ))
+        .unwrap_err();
+
+    let message = err.to_string();
+    assert!(
+        message.starts_with("Failed to fetch 1 of 2 components from SocialDB:\n - Bad: "),
+        "{message}"
+    );
+    assert!(!message.contains("Good"), "{message}");
+    good_component_mock.assert_hits(1);
+    // The batch and then the `Bad` component alone: 1 attempt + 2 retries each
+    rate_limited_mock.assert_hits(6);
+}
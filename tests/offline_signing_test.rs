@@ -0,0 +1,115 @@
+mod social_db_emulator;
+mod test_util;
+use social_db_emulator::SocialDbEmulator;
+use std::str::FromStr;
+use test_util::*;
+
+#[test]
+fn test_bos_socialdb_data_set_exports_unsigned_transaction_and_sends_signature() {
+    let emulator = SocialDbEmulator::start();
+    emulator.storage_deposit("test.near", 100_000_000_000_000_000_000_000);
+
+    let config_dir = dirs::config_dir().unwrap().join("near-cli");
+    let backup_path = setup_config(&config_dir, &emulator.url());
+    // The transaction is exported on a connection whose key is not its network name, next to an
+    // unreachable `mainnet` connection
+    let config_path = config_dir.join("config.toml");
+    let config = std::fs::read_to_string(&config_path).unwrap();
+    let decoy_connection = config
+        .replace(&emulator.url(), "http://127.0.0.1:1/")
+        .split_once("[network_connection.mainnet]")
+        .unwrap()
+        .1
+        .to_owned();
+    std::fs::write(
+        &config_path,
+        format!(
+            "{}\n[network_connection.mainnet]{decoy_connection}",
+            config.replace(
+                "[network_connection.mainnet]",
+                "[network_connection.mainnet-emulator]"
+            )
+        ),
+    )
+    .unwrap();
+    let unsigned_transaction_path = std::env::temp_dir().join(format!(
+        "bos-cli-test-unsigned-transaction-{}.json",
+        std::process::id()
+    ));
+
+    bos_command()
+        .args([
+            "social-db",
+            "data",
+            "set",
+            "test.near/profile/name",
+            "--yes",
+            "with-text",
+            "Alice",
+            "sign-as",
+            "test.near",
+            "--export-unsigned-transaction",
+            unsigned_transaction_path.to_str().unwrap(),
+            "--signer-public-key",
+            "ed25519:7fvCiaE4NTmhexo8fDoa3CFNupL6mvJmNjL1hydN65fm",
+            "network-config",
+            "mainnet-emulator",
+        ])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("The unsigned transaction <"));
+    assert!(emulator.transactions().is_empty());
+
+    let exported: serde_json::Value =
+        serde_json::from_slice(&std::fs::read(&unsigned_transaction_path).unwrap()).unwrap();
+    assert_eq!(exported["network_name"], "mainnet");
+    assert_eq!(exported["connection_name"], "mainnet-emulator");
+    assert_eq!(exported["operation"]["kind"], "data-set");
+    let transaction_hash =
+        near_primitives::hash::CryptoHash::from_str(exported["transaction_hash"].as_str().unwrap())
+            .unwrap();
+    let signature = near_crypto::SecretKey::from_str(
+        "ed25519:VzeoRptTNGWeXwq3JNLdo8XqoBKvjSXyV5VxjvxPyzsiGmwo2Vu6LTiBujoQSXYjF8khQS5r3SSQK3xV8uomjv7",
+    )
+    .unwrap()
+    .sign(transaction_hash.as_ref());
+
+    bos_command()
+        .args([
+            "transaction",
+            "send-signed",
+            unsigned_transaction_path.to_str().unwrap(),
+            "--signature",
+            &signature.to_string(),
+        ])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains(
+            "Keys successfully installed on <test.near>",
+        ));
+    assert_eq!(
+        emulator.get(&["test.near/profile/name"]),
+        serde_json::json!({ "test.near": { "profile": { "name": "Alice" } } })
+    );
+
+    // A signature made by another key is rejected before broadcasting
+    let other_signature = near_crypto::SecretKey::from_random(near_crypto::KeyType::ED25519)
+        .sign(transaction_hash.as_ref());
+    bos_command()
+        .args([
+            "transaction",
+            "send-signed",
+            unsigned_transaction_path.to_str().unwrap(),
+            "--signature",
+            &other_signature.to_string(),
+        ])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains(
+            "The signature does not match the key",
+        ));
+    assert_eq!(emulator.transactions().len(), 1);
+
+    std::fs::remove_file(&unsigned_transaction_path).unwrap();
+    restore_config(&config_dir, backup_path);
+}
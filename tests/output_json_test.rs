@@ -0,0 +1,117 @@
+mod social_db_emulator;
+mod test_util;
+use social_db_emulator::SocialDbEmulator;
+use test_util::*;
+
+fn stdout_json(output: &std::process::Output) -> serde_json::Value {
+    serde_json::from_slice(&output.stdout).unwrap_or_else(|err| {
+        panic!(
+            "stdout is not a single JSON document ({err}):\n{}",
+            String::from_utf8_lossy(&output.stdout)
+        )
+    })
+}
+
+#[test]
+fn test_bos_output_json_prints_single_json_result_on_stdout() {
+    let emulator = SocialDbEmulator::start();
+    emulator.storage_deposit("test.near", 100_000_000_000_000_000_000_000);
+    emulator.set(serde_json::json!({
+        "test.near": { "profile": { "name": "Alice \"A\"", "tags": { "rust": "" } } }
+    }));
+
+    let config_dir = dirs::config_dir().unwrap().join("near-cli");
+    let backup_path = setup_config(&config_dir, &emulator.url());
+    let temp_dir = setup_temp_dir();
+    std::fs::write(
+        temp_dir.path().join("src").join("Header.jsx"),
+        "return <h1>Hello</h1>;",
+    )
+    .unwrap();
+
+    let output = bos_command()
+        .current_dir(temp_dir.path())
+        .args([
+            "--output",
+            "json",
+            "components",
+            "deploy",
+            "test.near",
+            "sign-as",
+            "test.near",
+            "network-config",
+            "mainnet",
+            "sign-with-plaintext-private-key",
+            "--signer-public-key",
+            "ed25519:7fvCiaE4NTmhexo8fDoa3CFNupL6mvJmNjL1hydN65fm",
+            "--signer-private-key",
+            "ed25519:VzeoRptTNGWeXwq3JNLdo8XqoBKvjSXyV5VxjvxPyzsiGmwo2Vu6LTiBujoQSXYjF8khQS5r3SSQK3xV8uomjv7",
+            "send",
+        ])
+        .assert()
+        .success()
+        .get_output()
+        .clone();
+    let result = stdout_json(&output);
+    assert_eq!(result["account_id"], "test.near");
+    assert_eq!(result["deployed_components"], serde_json::json!(["Header"]));
+    assert_eq!(
+        result["transaction_hash"],
+        emulator.transactions()[0]["hash"]
+    );
+
+    let output = bos_command()
+        .args([
+            "--output",
+            "json",
+            "social-db",
+            "data",
+            "view",
+            "test.near/profile/**",
+            "--format",
+            "flat",
+            "network-config",
+            "mainnet",
+            "now",
+        ])
+        .assert()
+        .success()
+        .get_output()
+        .clone();
+    assert_eq!(
+        stdout_json(&output),
+        serde_json::json!({
+            "test.near": { "profile": { "name": "Alice \"A\"", "tags": { "rust": "" } } }
+        })
+    );
+
+    // Failures are reported as JSON too
+    let output = bos_command()
+        .args([
+            "--output",
+            "json",
+            "social-db",
+            "permissions",
+            "check",
+            "--predecessor",
+            "alice.near",
+            "--key",
+            "test.near/widget/**",
+            "--public-key",
+            "ed25519:7fvCiaE4NTmhexo8fDoa3CFNupL6mvJmNjL1hydN65fm",
+            "network-config",
+            "mainnet",
+        ])
+        .assert()
+        .failure()
+        .get_output()
+        .clone();
+    let result = stdout_json(&output);
+    assert_eq!(result["status"], "error");
+    assert!(result["error"]
+        .as_str()
+        .unwrap()
+        .contains("Exactly one of `--public-key` or `--predecessor` must be specified"));
+
+    restore_config(&config_dir, backup_path);
+}
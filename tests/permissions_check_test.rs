@@ -0,0 +1,34 @@
+mod test_util;
+use test_util::*;
+
+#[test]
+fn test_bos_socialdb_permissions_check_not_granted() {
+    // The mocked `is_write_permission_granted` always returns `false`
+    let mut server = setup_mock_server();
+    server = mock_unmatched(server);
+
+    let config_dir = dirs::config_dir().unwrap().join("near-cli");
+    let backup_path = setup_config(&config_dir, &server.url("/"));
+
+    let mut cmd = bos_command();
+
+    cmd.args([
+        "social-db",
+        "permissions",
+        "check",
+        "--predecessor",
+        "bob.near",
+        "--key",
+        "test.near/widget/**,test.near/profile/**",
+        "network-config",
+        "mainnet",
+    ])
+    .assert()
+    .failure()
+    .stdout("test.near/widget/**: no\ntest.near/profile/**: no\n")
+    .stderr(predicates::str::contains(
+        "Write permission is not granted for 2 of 2 key(s)",
+    ));
+
+    restore_config(&config_dir, backup_path);
+}
@@ -0,0 +1,100 @@
+mod social_db_emulator;
+mod test_util;
+use social_db_emulator::SocialDbEmulator;
+use test_util::*;
+
+const CI_PUBLIC_KEY: &str = "ed25519:6E8sCci9badyRkXb3JoRpBj5p8C6Tw41ELDZoiihKEtp";
+
+#[test]
+fn test_bos_socialdb_permissions_list_and_revoke() {
+    let emulator = SocialDbEmulator::start();
+    emulator.add_access_key(
+        "test.near",
+        CI_PUBLIC_KEY,
+        serde_json::json!({
+            "FunctionCall": {
+                "allowance": "1000000000000000000000000",
+                "receiver_id": "social.near",
+                "method_names": ["set"],
+            }
+        }),
+    );
+    emulator.grant_write_permission_to_public_key(
+        "test.near",
+        CI_PUBLIC_KEY,
+        &["test.near/widget"],
+    );
+
+    let config_dir = dirs::config_dir().unwrap().join("near-cli");
+    let backup_path = setup_config(&config_dir, &emulator.url());
+
+    let list = || {
+        bos_command()
+            .args([
+                "social-db",
+                "permissions",
+                "list",
+                "test.near",
+                "widget",
+                "network-config",
+                "mainnet",
+            ])
+            .assert()
+            .success()
+    };
+
+    list().stdout(format!("  public key   {CI_PUBLIC_KEY}\n"));
+
+    bos_command()
+        .args([
+            "social-db",
+            "permissions",
+            "revoke-write-access",
+            CI_PUBLIC_KEY,
+            "sign-as",
+            "test.near",
+            "network-config",
+            "mainnet",
+            "sign-with-plaintext-private-key",
+            "--signer-public-key",
+            "ed25519:7fvCiaE4NTmhexo8fDoa3CFNupL6mvJmNjL1hydN65fm",
+            "--signer-private-key",
+            "ed25519:VzeoRptTNGWeXwq3JNLdo8XqoBKvjSXyV5VxjvxPyzsiGmwo2Vu6LTiBujoQSXYjF8khQS5r3SSQK3xV8uomjv7",
+            "send",
+        ])
+        .assert()
+        .success()
+        .stderr(predicates::str::contains(format!(
+            "<test.near> has revoked the write access of public key <{CI_PUBLIC_KEY}> by deleting the key"
+        )));
+    assert_eq!(emulator.access_key("test.near", CI_PUBLIC_KEY), None);
+
+    list().stdout("").stderr(predicates::str::contains(
+        "No function-call access keys of <test.near> or known accounts have write permission to <test.near/widget>",
+    ));
+
+    // The signing key is a full access key, so it is never deleted
+    bos_command()
+        .args([
+            "social-db",
+            "permissions",
+            "revoke-write-access",
+            "ed25519:7fvCiaE4NTmhexo8fDoa3CFNupL6mvJmNjL1hydN65fm",
+            "sign-as",
+            "test.near",
+            "network-config",
+            "mainnet",
+            "sign-with-plaintext-private-key",
+            "--signer-public-key",
+            "ed25519:7fvCiaE4NTmhexo8fDoa3CFNupL6mvJmNjL1hydN65fm",
+            "--signer-private-key",
+            "ed25519:VzeoRptTNGWeXwq3JNLdo8XqoBKvjSXyV5VxjvxPyzsiGmwo2Vu6LTiBujoQSXYjF8khQS5r3SSQK3xV8uomjv7",
+            "send",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("is not a function-call access key"));
+    assert_eq!(emulator.transactions().len(), 1);
+
+    restore_config(&config_dir, backup_path);
+}
@@ -0,0 +1,71 @@
+mod social_db_emulator;
+mod test_util;
+use social_db_emulator::SocialDbEmulator;
+use test_util::*;
+
+#[test]
+fn test_bos_profile_apply_replaces_profile_with_file() {
+    let emulator = SocialDbEmulator::start();
+    emulator.storage_deposit("test.near", 100_000_000_000_000_000_000_000);
+    emulator.set(serde_json::json!({
+        "test.near": {
+            "profile": {
+                "name": "Old name",
+                "description": "To be removed",
+                "linktree": { "github": "alice", "twitter": "alice_tw" },
+            }
+        }
+    }));
+
+    let config_dir = dirs::config_dir().unwrap().join("near-cli");
+    let backup_path = setup_config(&config_dir, &emulator.url());
+    let temp_dir = setup_temp_dir();
+    let profile_path = temp_dir.path().join("profile.yaml");
+    std::fs::write(
+        &profile_path,
+        "name: New name\nlinktree:\n  github: alice\ntags:\n  rust: \"\"\n",
+    )
+    .unwrap();
+
+    bos_command()
+        .args([
+            "social-db",
+            "manage-profile",
+            "apply",
+            profile_path.to_str().unwrap(),
+            "test.near",
+            "--yes",
+            "sign-as",
+            "test.near",
+            "network-config",
+            "mainnet",
+            "sign-with-plaintext-private-key",
+            "--signer-public-key",
+            "ed25519:7fvCiaE4NTmhexo8fDoa3CFNupL6mvJmNjL1hydN65fm",
+            "--signer-private-key",
+            "ed25519:VzeoRptTNGWeXwq3JNLdo8XqoBKvjSXyV5VxjvxPyzsiGmwo2Vu6LTiBujoQSXYjF8khQS5r3SSQK3xV8uomjv7",
+            "send",
+        ])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("\"test.near/profile/description\": \"To be removed\""))
+        .stdout(predicates::str::contains(
+            "Keys successfully installed on <test.near>",
+        ));
+
+    assert_eq!(emulator.transactions().len(), 1);
+    assert_eq!(
+        emulator.get(&["test.near/profile/**"]),
+        serde_json::json!({
+            "test.near": {
+                "profile": {
+                    "name": "New name",
+                    "linktree": { "github": "alice" },
+                    "tags": { "rust": "" },
+                }
+            }
+        })
+    );
+
+    restore_config(&config_dir, backup_path);
+}
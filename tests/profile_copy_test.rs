@@ -0,0 +1,78 @@
+mod social_db_emulator;
+mod test_util;
+use social_db_emulator::SocialDbEmulator;
+use test_util::*;
+
+#[test]
+fn test_bos_profile_copy_rewrites_linktree_and_images() {
+    let emulator = SocialDbEmulator::start();
+    emulator.storage_deposit("to.near", 100_000_000_000_000_000_000_000);
+    emulator.set(serde_json::json!({
+        "from.near": {
+            "profile": {
+                "name": "Alice",
+                "image": { "ipfs_cid": "bafkreiold" },
+                "backgroundImage": { "ipfs_cid": "bafkreioldbg" },
+                "linktree": { "website": "alice.example.com", "twitter": "alice_tw", "github": "alice" },
+            }
+        },
+        "to.near": {
+            "profile": { "description": "To be removed" }
+        }
+    }));
+    // A cleared linktree entry is returned as null
+    emulator.set_keeping_nulls(serde_json::json!({
+        "from.near": { "profile": { "linktree": { "telegram": null } } }
+    }));
+
+    let config_dir = dirs::config_dir().unwrap().join("near-cli");
+    let backup_path = setup_config(&config_dir, &emulator.url());
+
+    bos_command()
+        .args([
+            "social-db",
+            "manage-profile",
+            "copy",
+            "from.near",
+            "to.near",
+            "--linktree",
+            "website=project.example.com,twitter=",
+            "--image-url",
+            "https://example.com/logo.png",
+            "--background-image-ipfs-cid",
+            "bafkreinewbg",
+            "--yes",
+            "sign-as",
+            "to.near",
+            "network-config",
+            "mainnet",
+            "sign-with-plaintext-private-key",
+            "--signer-public-key",
+            "ed25519:7fvCiaE4NTmhexo8fDoa3CFNupL6mvJmNjL1hydN65fm",
+            "--signer-private-key",
+            "ed25519:VzeoRptTNGWeXwq3JNLdo8XqoBKvjSXyV5VxjvxPyzsiGmwo2Vu6LTiBujoQSXYjF8khQS5r3SSQK3xV8uomjv7",
+            "send",
+        ])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains(
+            "Keys successfully installed on <to.near>",
+        ));
+
+    assert_eq!(emulator.transactions().len(), 1);
+    assert_eq!(
+        emulator.get(&["to.near/profile/**"]),
+        serde_json::json!({
+            "to.near": {
+                "profile": {
+                    "name": "Alice",
+                    "image": { "url": "https://example.com/logo.png" },
+                    "backgroundImage": { "ipfs_cid": "bafkreinewbg" },
+                    "linktree": { "website": "project.example.com", "github": "alice" },
+                }
+            }
+        })
+    );
+
+    restore_config(&config_dir, backup_path);
+}
@@ -0,0 +1,59 @@
+mod social_db_emulator;
+mod test_util;
+use social_db_emulator::SocialDbEmulator;
+use test_util::*;
+
+#[test]
+fn test_bos_profile_view_as_json_prints_serialized_profile() {
+    let emulator = SocialDbEmulator::start();
+    // A cleared linktree entry is returned as null
+    emulator.set_keeping_nulls(serde_json::json!({
+        "test.near": {
+            "profile": {
+                "name": "Alice \"A\"",
+                "description": "Line 1\nLine 2",
+                "image": { "nft": { "contractId": "nft.near", "tokenId": "1" } },
+                "linktree": { "github": "alice", "telegram": null },
+                "horizon_tagline": "Builder",
+            }
+        }
+    }));
+
+    let config_dir = dirs::config_dir().unwrap().join("near-cli");
+    let backup_path = setup_config(&config_dir, &emulator.url());
+
+    let output = bos_command()
+        .args([
+            "social-db",
+            "manage-profile",
+            "view-profile",
+            "test.near",
+            "as-json",
+            "network-config",
+            "mainnet",
+            "now",
+        ])
+        .assert()
+        .success()
+        .get_output()
+        .clone();
+    let snapshot: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(snapshot["account_id"], "test.near");
+    assert_eq!(
+        snapshot["block_hash"],
+        "GGJQ8yjmo7aEoj8ZpAhGehnq9BSWFx4xswHYzDwwAP2n"
+    );
+    assert!(snapshot["block_height"].is_u64());
+    assert_eq!(
+        snapshot["profile"],
+        serde_json::json!({
+            "name": "Alice \"A\"",
+            "image": { "nft": { "contractId": "nft.near", "tokenId": "1" } },
+            "description": "Line 1\nLine 2",
+            "linktree": { "github": "alice", "telegram": null },
+            "horizon_tagline": "Builder",
+        })
+    );
+
+    restore_config(&config_dir, backup_path);
+}
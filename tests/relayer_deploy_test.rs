@@ -0,0 +1,89 @@
+mod social_db_emulator;
+mod test_util;
+use social_db_emulator::SocialDbEmulator;
+use test_util::*;
+
+fn relay_deploy(
+    temp_dir: &std::path::Path,
+    relayer_url: &str,
+    global_args: &[&str],
+) -> assert_cmd::assert::Assert {
+    bos_command()
+        .current_dir(temp_dir)
+        .args(global_args)
+        .args([
+            "components",
+            "deploy",
+            "test.near",
+            "relay-as",
+            "test.near",
+            "--relayer-url",
+            relayer_url,
+            "network-config",
+            "mainnet",
+            "sign-with-plaintext-private-key",
+            "--signer-public-key",
+            "ed25519:7fvCiaE4NTmhexo8fDoa3CFNupL6mvJmNjL1hydN65fm",
+            "--signer-private-key",
+            "ed25519:VzeoRptTNGWeXwq3JNLdo8XqoBKvjSXyV5VxjvxPyzsiGmwo2Vu6LTiBujoQSXYjF8khQS5r3SSQK3xV8uomjv7",
+            "send",
+        ])
+        .assert()
+}
+
+#[test]
+fn test_bos_components_deploy_relay_as_sends_delegate_action_to_relayer() {
+    let emulator = SocialDbEmulator::start();
+
+    let config_dir = dirs::config_dir().unwrap().join("near-cli");
+    let backup_path = setup_config(&config_dir, &emulator.url());
+    let temp_dir = setup_temp_dir();
+    std::fs::write(
+        temp_dir.path().join("src").join("Header.jsx"),
+        "return <h1>Hello, relayer</h1>;",
+    )
+    .unwrap();
+
+    // The relayer does not attach a deposit, so the storage has to be prepaid
+    relay_deploy(temp_dir.path(), &emulator.relayer_url(), &[])
+        .failure()
+        .stderr(predicates::str::contains(
+            "bos social-db prepaid-storage deposit test.near",
+        ));
+    assert!(emulator.transactions().is_empty());
+
+    emulator.storage_deposit("test.near", 100_000_000_000_000_000_000_000);
+    relay_deploy(temp_dir.path(), "http://127.0.0.1:1/relay", &[])
+        .failure()
+        .stderr(predicates::str::contains(
+            "Failed to send the delegate action to the relayer <http://127.0.0.1:1/relay>",
+        ));
+    assert!(emulator.transactions().is_empty());
+
+    // The deploy is reported from the transaction in the relayer response
+    let output = relay_deploy(
+        temp_dir.path(),
+        &emulator.relayer_url(),
+        &["--output", "json"],
+    )
+    .success()
+    .get_output()
+    .clone();
+    let result: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(result["deployed_components"], serde_json::json!(["Header"]));
+
+    let transactions = emulator.transactions();
+    assert_eq!(transactions.len(), 1);
+    assert_eq!(transactions[0]["signer_id"], "relayer.near");
+    assert_eq!(transactions[0]["receiver_id"], "test.near");
+    assert!(result["transaction_hash"].is_string());
+    assert_eq!(result["transaction_hash"], transactions[0]["hash"]);
+    assert_eq!(
+        emulator.get(&["test.near/widget/Header"]),
+        serde_json::json!({
+            "test.near": { "widget": { "Header": "return <h1>Hello, relayer</h1>;" } }
+        })
+    );
+
+    restore_config(&config_dir, backup_path);
+}
@@ -0,0 +1,71 @@
+mod social_db_emulator;
+mod test_util;
+use social_db_emulator::SocialDbEmulator;
+use test_util::*;
+
+#[test]
+fn test_bos_components_download_fails_over_to_fallback_rpc_url() {
+    // The primary endpoint passes the health check, but fails the requests afterwards
+    let flaky_emulator = SocialDbEmulator::start();
+    flaky_emulator.fail_requests_except_status();
+
+    let emulator = SocialDbEmulator::start();
+    emulator.set(serde_json::json!({
+        "test.near": { "widget": { "a": "code a", "b": "code b" } }
+    }));
+
+    let config_dir = dirs::config_dir().unwrap().join("near-cli");
+    let backup_path = setup_config(&config_dir, &flaky_emulator.url());
+    let work_dir = tempfile::tempdir().unwrap();
+
+    let assert = bos_command()
+        .current_dir(work_dir.path())
+        .args([
+            "--teach-me",
+            "--rpc-fallback-urls",
+            &format!("mainnet={}", emulator.url()),
+            "components",
+            "download",
+            "test.near",
+            "network-config",
+            "mainnet",
+        ])
+        .assert();
+
+    // The SocialDB reads of the other commands fail over too
+    bos_command()
+        .args([
+            "--rpc-fallback-urls",
+            &format!("mainnet={}", emulator.url()),
+            "social-db",
+            "data",
+            "keys",
+            "test.near/widget/*",
+            "network-config",
+            "mainnet",
+            "now",
+        ])
+        .assert()
+        .success()
+        .stdout("test.near\n└── widget\n    ├── a\n    └── b\n");
+
+    restore_config(&config_dir, backup_path);
+
+    assert
+        .success()
+        .stdout(predicates::str::contains(format!(
+            "Selected RPC endpoint <{}>",
+            flaky_emulator.url()
+        )))
+        .stdout(predicates::str::contains(format!(
+            "Switching to the fallback RPC endpoint <{}>",
+            emulator.url()
+        )));
+    assert_eq!(
+        std::fs::read_to_string(work_dir.path().join("src/b.jsx")).unwrap(),
+        "code b"
+    );
+    // The first failed request marks the primary endpoint as unhealthy, so the following requests
+    // of each run skip it right away
+    assert_eq!(flaky_emulator.failed_requests(), 2);
+}
@@ -0,0 +1,102 @@
+mod social_db_emulator;
+mod test_util;
+use social_db_emulator::SocialDbEmulator;
+use test_util::*;
+
+const PUBLIC_KEY: &str = "ed25519:7fvCiaE4NTmhexo8fDoa3CFNupL6mvJmNjL1hydN65fm";
+const PRIVATE_KEY: &str = "ed25519:VzeoRptTNGWeXwq3JNLdo8XqoBKvjSXyV5VxjvxPyzsiGmwo2Vu6LTiBujoQSXYjF8khQS5r3SSQK3xV8uomjv7";
+
+/// The whole sandbox flow, offline: the emulator stands in for the sandbox node and the
+/// SocialDB contract, so the deployed wasm file is only a stub fixture.
+#[test]
+fn test_bos_sandbox_deploy_social_db_end_to_end() {
+    let emulator = SocialDbEmulator::start_without_contract();
+
+    let config_dir = dirs::config_dir().unwrap().join("near-cli");
+    let backup_path = setup_config(&config_dir, "http://127.0.0.1:1/");
+
+    let bos = |args: &[&str]| {
+        let mut cmd = bos_command();
+        cmd.args(args);
+        cmd.assert()
+    };
+
+    bos(&["sandbox", "add-network", "--rpc-url", &emulator.url()]).success();
+
+    bos(&[
+        "sandbox",
+        "deploy-social-db",
+        "social.test.near",
+        "--wasm-file",
+        "tests/fixtures/social_db_stub.wasm",
+        "network-config",
+        "sandbox",
+        "sign-with-plaintext-private-key",
+        "--signer-public-key",
+        PUBLIC_KEY,
+        "--signer-private-key",
+        PRIVATE_KEY,
+        "send",
+    ])
+    .success()
+    .stderr(predicates::str::contains(
+        "The SocialDB contract is deployed to <social.test.near> on the <sandbox> network",
+    ));
+
+    bos(&[
+        "sandbox",
+        "fund-account",
+        "alice.test.near",
+        "--public-key",
+        PUBLIC_KEY,
+        "network-config",
+        "sandbox",
+        "sign-with-plaintext-private-key",
+        "--signer-public-key",
+        PUBLIC_KEY,
+        "--signer-private-key",
+        PRIVATE_KEY,
+        "send",
+    ])
+    .success();
+
+    bos(&[
+        "social-db",
+        "data",
+        "set",
+        "alice.test.near/profile/name",
+        "--yes",
+        "with-text",
+        "Alice",
+        "sign-as",
+        "alice.test.near",
+        "network-config",
+        "sandbox",
+        "sign-with-plaintext-private-key",
+        "--signer-public-key",
+        PUBLIC_KEY,
+        "--signer-private-key",
+        PRIVATE_KEY,
+        "send",
+    ])
+    .success();
+
+    let transactions = emulator.transactions();
+
+    restore_config(&config_dir, backup_path);
+
+    assert_eq!(
+        emulator.get(&["alice.test.near/profile/name"]),
+        serde_json::json!({ "alice.test.near": { "profile": { "name": "Alice" } } })
+    );
+    let deploy_transaction = &transactions[0];
+    assert_eq!(deploy_transaction["signer_id"], "test.near");
+    assert_eq!(deploy_transaction["receiver_id"], "social.test.near");
+    let method_names = deploy_transaction["actions"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter_map(|action| action["FunctionCall"]["method_name"].as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(method_names, ["new", "set_status"]);
+}
@@ -0,0 +1,35 @@
+mod test_util;
+use test_util::*;
+
+#[test]
+fn test_bos_sandbox_add_network() {
+    let config_dir = dirs::config_dir().unwrap().join("near-cli");
+    let backup_path = setup_config(&config_dir, "http://127.0.0.1:1/");
+
+    let mut cmd = bos_command();
+
+    cmd.args([
+        "sandbox",
+        "add-network",
+        "--network-name",
+        "localnet",
+        "--rpc-url",
+        "http://127.0.0.1:3030/",
+    ])
+    .assert()
+    .success();
+
+    let config = std::fs::read_to_string(config_dir.join("config.toml")).unwrap();
+    let config: toml::Value = toml::from_str(&config).unwrap();
+    let localnet = &config["network_connection"]["localnet"];
+
+    restore_config(&config_dir, backup_path);
+
+    assert_eq!(localnet["rpc_url"].as_str(), Some("http://127.0.0.1:3030/"));
+    assert_eq!(
+        localnet["near_social_db_contract_account_id"].as_str(),
+        Some("social.test.near")
+    );
+    // The existing networks are kept
+    assert!(config["network_connection"].get("mainnet").is_some());
+}
@@ -0,0 +1,66 @@
+mod social_db_emulator;
+mod test_util;
+use social_db_emulator::SocialDbEmulator;
+use test_util::*;
+
+#[test]
+fn test_bos_project_setup_ci_key_adds_key_and_grants_write_permission() {
+    let emulator = SocialDbEmulator::start();
+
+    let config_dir = dirs::config_dir().unwrap().join("near-cli");
+    let backup_path = setup_config(&config_dir, &emulator.url());
+
+    let output = bos_command()
+        .args([
+            "--output",
+            "json",
+            "project",
+            "setup-ci-key",
+            "test.near",
+            "--storage-deposit",
+            "0.5 NEAR",
+            "network-config",
+            "mainnet",
+            "sign-with-plaintext-private-key",
+            "--signer-public-key",
+            "ed25519:7fvCiaE4NTmhexo8fDoa3CFNupL6mvJmNjL1hydN65fm",
+            "--signer-private-key",
+            "ed25519:VzeoRptTNGWeXwq3JNLdo8XqoBKvjSXyV5VxjvxPyzsiGmwo2Vu6LTiBujoQSXYjF8khQS5r3SSQK3xV8uomjv7",
+            "send",
+        ])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let result: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    let public_key = result["public_key"].as_str().unwrap();
+
+    // Both the AddKey and the `grant_write_permission` transactions are signed by the account
+    let transactions = emulator.transactions();
+    assert_eq!(transactions.len(), 2);
+    assert_eq!(transactions[0]["receiver_id"], "test.near");
+    assert_eq!(transactions[1]["receiver_id"], "social.near");
+    assert_eq!(result["add_key_transaction_hash"], transactions[0]["hash"]);
+
+    assert_eq!(
+        emulator.access_key("test.near", public_key),
+        Some(serde_json::json!({
+            "FunctionCall": {
+                "allowance": "1000000000000000000000000",
+                "receiver_id": "social.near",
+                "method_names": ["set"],
+            }
+        }))
+    );
+    assert!(emulator.is_write_permission_granted_to_public_key(public_key, "test.near/widget/Nav"));
+    assert!(
+        !emulator.is_write_permission_granted_to_public_key(public_key, "test.near/profile/name")
+    );
+    assert_eq!(
+        emulator.storage_balance("test.near"),
+        500_000_000_000_000_000_000_000
+    );
+
+    restore_config(&config_dir, backup_path);
+}
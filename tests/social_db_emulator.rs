@@ -0,0 +1,1078 @@
+#![allow(dead_code)]
+
+//! An in-memory SocialDB emulator that speaks the subset of NEAR JSON-RPC used by `bos`.
+//!
+//! Unlike the static `httpmock` stubs in `test_util`, the emulator keeps the SocialDB state
+//! between requests: `set` transactions (including `null` deletes) and permission grants sent
+//! with `broadcast_tx_commit` are applied to the in-memory tree, and the following `query`
+//! calls (`get`, `keys`, `get_account_storage`, `storage_balance_of`,
+//! `is_write_permission_granted`) observe the result.
+//!
+//! Storage is tracked but not enforced, so tests do not need to pre-fund accounts precisely.
+//!
+//! The keys signing the transactions are treated as full access keys, while the keys added with
+//! [`SocialDbEmulator::add_access_key`] or with `AddKey` actions keep their permission until they
+//! are removed with a `DeleteKey` action.
+//!
+//! Sputnik DAO accounts registered with [`SocialDbEmulator::add_dao`] answer `get_policy` and
+//! record the proposals sent with `add_proposal` (they are never executed).
+//!
+//! [`SocialDbEmulator::relayer_url`] accepts signed delegate actions like a meta transaction
+//! relayer: they are wrapped into a `relayer.near` transaction and executed on behalf of the sender.
+
+use std::collections::{BTreeMap, HashMap};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+
+use base64::prelude::*;
+use near_primitives::borsh::BorshDeserialize;
+use serde_json::{json, Map, Value};
+
+const BLOCK_HASH: &str = "GGJQ8yjmo7aEoj8ZpAhGehnq9BSWFx4xswHYzDwwAP2n";
+const STORAGE_COST_PER_BYTE: u128 = 10u128.pow(19);
+
+/// A SocialDB emulator serving JSON-RPC requests on a local port until it is dropped.
+pub struct SocialDbEmulator {
+    address: std::net::SocketAddr,
+    state: Arc<Mutex<State>>,
+}
+
+impl SocialDbEmulator {
+    /// Starts the emulator for the `social.near` contract.
+    pub fn start() -> Self {
+        Self::start_with_contract("social.near")
+    }
+
+    /// Starts the emulator without a SocialDB contract, until a transaction deploys one
+    /// (see `bos sandbox deploy-social-db`).
+    pub fn start_without_contract() -> Self {
+        Self::start_with_contract("")
+    }
+
+    /// Starts the emulator for a SocialDB contract deployed to `contract_account_id`.
+    pub fn start_with_contract(contract_account_id: &str) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("Failed to bind the emulator port");
+        let address = listener.local_addr().unwrap();
+        let state = Arc::new(Mutex::new(State::new(contract_account_id)));
+
+        let server_state = state.clone();
+        std::thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let state = server_state.clone();
+                std::thread::spawn(move || serve_connection(stream, state));
+            }
+        });
+
+        Self { address, state }
+    }
+
+    /// The RPC URL to put into the near CLI config (see `test_util::setup_config`).
+    pub fn url(&self) -> String {
+        format!("http://{}/", self.address)
+    }
+
+    /// The meta transaction relayer endpoint (see `bos components deploy ... relay-as`).
+    pub fn relayer_url(&self) -> String {
+        format!("http://{}/relay", self.address)
+    }
+
+    /// Writes `data` (in the SocialDB `set` format), bypassing the permission checks.
+    pub fn set(&self, data: Value) {
+        self.write(data, false);
+    }
+
+    /// Writes `data` like [`Self::set`], but stores the `null` values instead of removing the
+    /// keys, so `get` returns them the way SocialDB returns cleared entries.
+    pub fn set_keeping_nulls(&self, data: Value) {
+        self.write(data, true);
+    }
+
+    fn write(&self, data: Value, keep_nulls: bool) {
+        let mut state = self.state.lock().unwrap();
+        state.block_height += 1;
+        let block_height = state.block_height;
+        let State {
+            root, next_node_id, ..
+        } = &mut *state;
+        root.set(&data, block_height, next_node_id, keep_nulls);
+    }
+
+    /// Returns the values matching the key patterns, like the contract `get` method.
+    pub fn get(&self, keys: &[&str]) -> Value {
+        let state = self.state.lock().unwrap();
+        let keys = keys.iter().map(|key| key.to_string()).collect::<Vec<_>>();
+        state.root.get(&keys, false)
+    }
+
+    /// Adds `deposit` yoctoNEAR to the storage balance of `account_id`.
+    pub fn storage_deposit(&self, account_id: &str, deposit: u128) {
+        *self
+            .state
+            .lock()
+            .unwrap()
+            .storage_balances
+            .entry(account_id.to_owned())
+            .or_default() += deposit;
+    }
+
+    /// Adds an access key with the given `AccessKeyPermissionView` (e.g. `"FullAccess"`) to `account_id`.
+    pub fn add_access_key(&self, account_id: &str, public_key: &str, permission: Value) {
+        self.state
+            .lock()
+            .unwrap()
+            .access_keys
+            .insert((account_id.to_owned(), public_key.to_owned()), permission);
+    }
+
+    /// Returns the permission of an access key added to `account_id`, if the key was not deleted.
+    pub fn access_key(&self, account_id: &str, public_key: &str) -> Option<Value> {
+        self.state
+            .lock()
+            .unwrap()
+            .access_keys
+            .get(&(account_id.to_owned(), public_key.to_owned()))
+            .cloned()
+    }
+
+    /// Grants `public_key` write access to the key prefixes of `account_id`, bypassing the transaction.
+    pub fn grant_write_permission_to_public_key(
+        &self,
+        account_id: &str,
+        public_key: &str,
+        keys: &[&str],
+    ) {
+        self.state
+            .lock()
+            .unwrap()
+            .permissions
+            .entry((
+                account_id.to_owned(),
+                PermissionKey::PublicKey(public_key.to_owned()),
+            ))
+            .or_default()
+            .extend(keys.iter().map(|key| key.to_string()));
+    }
+
+    /// Returns whether `public_key` may write `key`, like the contract `is_write_permission_granted` method.
+    pub fn is_write_permission_granted_to_public_key(&self, public_key: &str, key: &str) -> bool {
+        self.state
+            .lock()
+            .unwrap()
+            .is_write_permission_granted(&PermissionKey::PublicKey(public_key.to_owned()), key)
+    }
+
+    /// Returns the storage balance deposited for `account_id`.
+    pub fn storage_balance(&self, account_id: &str) -> u128 {
+        self.state
+            .lock()
+            .unwrap()
+            .storage_balances
+            .get(account_id)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Registers a Sputnik DAO contract at `dao_account_id` requiring `proposal_bond` yoctoNEAR.
+    pub fn add_dao(&self, dao_account_id: &str, proposal_bond: u128) {
+        self.state.lock().unwrap().daos.insert(
+            dao_account_id.to_owned(),
+            Dao {
+                proposal_bond,
+                proposals: Vec::new(),
+            },
+        );
+    }
+
+    /// Returns the proposals added to the DAO so far.
+    pub fn proposals(&self, dao_account_id: &str) -> Vec<Value> {
+        self.state.lock().unwrap().daos[dao_account_id]
+            .proposals
+            .clone()
+    }
+
+    /// Returns the `SignedTransactionView`s of all transactions received so far.
+    pub fn transactions(&self) -> Vec<Value> {
+        self.state.lock().unwrap().transactions.clone()
+    }
+
+    /// Makes the emulator a flaky node: it passes the `status` health check, but fails every
+    /// other request with `503 Service Unavailable`.
+    pub fn fail_requests_except_status(&self) {
+        self.state.lock().unwrap().fail_requests_except_status = true;
+    }
+
+    /// The number of requests failed since [`Self::fail_requests_except_status`].
+    pub fn failed_requests(&self) -> usize {
+        self.state.lock().unwrap().failed_requests
+    }
+}
+
+struct State {
+    contract_account_id: String,
+    block_height: u64,
+    next_node_id: u64,
+    root: Node,
+    /// (account ID, permission key) -> granted key prefixes
+    permissions: HashMap<(String, PermissionKey), Vec<String>>,
+    storage_balances: HashMap<String, u128>,
+    nonces: HashMap<(String, String), u64>,
+    /// (account ID, public key) -> `AccessKeyPermissionView`
+    access_keys: HashMap<(String, String), Value>,
+    transactions: Vec<Value>,
+    daos: HashMap<String, Dao>,
+    fail_requests_except_status: bool,
+    failed_requests: usize,
+}
+
+struct Dao {
+    proposal_bond: u128,
+    proposals: Vec<Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum PermissionKey {
+    PredecessorId(String),
+    PublicKey(String),
+}
+
+impl State {
+    fn new(contract_account_id: &str) -> Self {
+        Self {
+            contract_account_id: contract_account_id.to_owned(),
+            block_height: 1,
+            next_node_id: 0,
+            root: Node::default(),
+            permissions: HashMap::new(),
+            storage_balances: HashMap::new(),
+            nonces: HashMap::new(),
+            access_keys: HashMap::new(),
+            transactions: Vec::new(),
+            daos: HashMap::new(),
+            fail_requests_except_status: false,
+            failed_requests: 0,
+        }
+    }
+
+    fn is_write_permission_granted(&self, permission_key: &PermissionKey, key: &str) -> bool {
+        let account_id = key.split('/').next().unwrap_or_default();
+        if let PermissionKey::PredecessorId(predecessor_id) = permission_key {
+            if predecessor_id == account_id {
+                return true;
+            }
+        }
+        self.permissions
+            .get(&(account_id.to_owned(), permission_key.clone()))
+            .is_some_and(|prefixes| {
+                prefixes.iter().any(|prefix| {
+                    key == prefix || key.starts_with(&format!("{}/", prefix.trim_end_matches('/')))
+                })
+            })
+    }
+
+    fn used_bytes(&self, account_id: &str) -> u64 {
+        self.root
+            .children
+            .get(account_id)
+            .map(|node| node.used_bytes(account_id))
+            .unwrap_or_default()
+    }
+
+    fn call_view_function(&self, method_name: &str, args: &Value) -> Result<Value, String> {
+        match method_name {
+            "get" => {
+                let keys = string_list(&args["keys"]);
+                let with_block_height = args["options"]["with_block_height"] == json!(true);
+                Ok(self.root.get(&keys, with_block_height))
+            }
+            "keys" => {
+                let keys = string_list(&args["keys"]);
+                let return_type = args["options"]["return_type"].as_str().unwrap_or("True");
+                let values_only = args["options"]["values_only"] == json!(true);
+                Ok(self.root.keys(&keys, return_type, values_only))
+            }
+            "storage_balance_of" => {
+                let account_id = args["account_id"].as_str().unwrap_or_default();
+                Ok(match self.storage_balances.get(account_id) {
+                    Some(total) => {
+                        let used = u128::from(self.used_bytes(account_id)) * STORAGE_COST_PER_BYTE;
+                        json!({
+                            "total": total.to_string(),
+                            "available": total.saturating_sub(used).to_string(),
+                        })
+                    }
+                    None => Value::Null,
+                })
+            }
+            "get_account_storage" => {
+                let account_id = args["account_id"].as_str().unwrap_or_default();
+                Ok(match self.storage_balances.get(account_id) {
+                    Some(total) => {
+                        let used_bytes = self.used_bytes(account_id);
+                        let total_bytes = (total / STORAGE_COST_PER_BYTE) as u64;
+                        json!({
+                            "used_bytes": used_bytes,
+                            "available_bytes": total_bytes.saturating_sub(used_bytes),
+                        })
+                    }
+                    None => Value::Null,
+                })
+            }
+            "is_write_permission_granted" => {
+                let key = args["key"].as_str().unwrap_or_default();
+                let permission_key = permission_key_from_args(args)?;
+                Ok(json!(self.is_write_permission_granted(&permission_key, key)))
+            }
+            _ => Err(format!("MethodNotFound: {method_name}")),
+        }
+    }
+
+    fn call_function(
+        &mut self,
+        predecessor_id: &str,
+        signer_public_key: &str,
+        method_name: &str,
+        args: &Value,
+        deposit: u128,
+    ) -> Result<(), String> {
+        match method_name {
+            "set" => {
+                let data = args["data"]
+                    .as_object()
+                    .ok_or("The `data` argument must be an object")?;
+                let permission_keys = [
+                    PermissionKey::PredecessorId(predecessor_id.to_owned()),
+                    PermissionKey::PublicKey(signer_public_key.to_owned()),
+                ];
+                for (account_id, account_data) in data {
+                    for (key, _) in leaves(account_id, account_data) {
+                        if !permission_keys.iter().any(|permission_key| {
+                            self.is_write_permission_granted(permission_key, &key)
+                        }) {
+                            return Err(format!(
+                                "Smart contract panicked: Permission denied for key {key}"
+                            ));
+                        }
+                    }
+                }
+                if deposit > 0 {
+                    let account_id = if data.contains_key(predecessor_id) {
+                        predecessor_id
+                    } else {
+                        data.keys()
+                            .next()
+                            .map(String::as_str)
+                            .unwrap_or(predecessor_id)
+                    };
+                    *self
+                        .storage_balances
+                        .entry(account_id.to_owned())
+                        .or_default() += deposit;
+                }
+                self.block_height += 1;
+                let State {
+                    root,
+                    next_node_id,
+                    block_height,
+                    ..
+                } = self;
+                root.set(&args["data"], *block_height, next_node_id, false);
+                Ok(())
+            }
+            "grant_write_permission" => {
+                let permission_key = permission_key_from_args(args)?;
+                let keys = string_list(&args["keys"]);
+                for key in &keys {
+                    if key.split('/').next() != Some(predecessor_id) {
+                        return Err(format!(
+                            "Smart contract panicked: The key {key} does not belong to {predecessor_id}"
+                        ));
+                    }
+                }
+                self.permissions
+                    .entry((predecessor_id.to_owned(), permission_key))
+                    .or_default()
+                    .extend(keys);
+                *self
+                    .storage_balances
+                    .entry(predecessor_id.to_owned())
+                    .or_default() += deposit;
+                Ok(())
+            }
+            "storage_deposit" => {
+                let account_id = args["account_id"].as_str().unwrap_or(predecessor_id);
+                *self
+                    .storage_balances
+                    .entry(account_id.to_owned())
+                    .or_default() += deposit;
+                Ok(())
+            }
+            // The contract initialization, the emulator state is always initialized and live
+            "new" | "set_status" => Ok(()),
+            _ => Err(format!("MethodNotFound: {method_name}")),
+        }
+    }
+
+    fn handle(&mut self, request: &Value) -> Value {
+        let id = request["id"].clone();
+        let params = &request["params"];
+        let result = match request["method"].as_str().unwrap_or_default() {
+            "query" => self.handle_query(params),
+            "broadcast_tx_commit" => self.handle_broadcast_tx_commit(params[0].as_str()),
+            "send_tx" => self.handle_broadcast_tx_commit(params["signed_tx_base64"].as_str()),
+            "status" => Ok(self.status()),
+            method => Err(json!({
+                "name": "REQUEST_VALIDATION_ERROR",
+                "cause": { "name": "METHOD_NOT_FOUND", "info": { "method_name": method } },
+                "code": -32601,
+                "message": "Method not found",
+            })),
+        };
+        match result {
+            Ok(result) => json!({ "jsonrpc": "2.0", "result": result, "id": id }),
+            Err(error) => json!({ "jsonrpc": "2.0", "error": error, "id": id }),
+        }
+    }
+
+    fn status(&self) -> Value {
+        json!({
+            "version": { "version": "emulator", "build": "emulator" },
+            "chain_id": "emulator",
+            "protocol_version": 73,
+            "latest_protocol_version": 73,
+            "validators": [],
+            "sync_info": {
+                "latest_block_hash": BLOCK_HASH,
+                "latest_block_height": self.block_height,
+                "latest_state_root": BLOCK_HASH,
+                "latest_block_time": "2024-01-01T00:00:00Z",
+                "syncing": false,
+                "earliest_block_hash": null,
+                "earliest_block_height": null,
+                "earliest_block_time": null,
+                "epoch_id": null,
+                "epoch_start_height": null,
+            },
+            "validator_account_id": null,
+            "validator_public_key": null,
+            "node_public_key": "ed25519:7fvCiaE4NTmhexo8fDoa3CFNupL6mvJmNjL1hydN65fm",
+            "node_key": null,
+            "uptime_sec": 0,
+            "genesis_hash": BLOCK_HASH,
+        })
+    }
+
+    fn handle_query(&mut self, params: &Value) -> Result<Value, Value> {
+        let block_height = self.block_height;
+        let account_id = params["account_id"].as_str().unwrap_or_default();
+        match params["request_type"].as_str().unwrap_or_default() {
+            "call_function" if self.daos.contains_key(account_id) => {
+                let method_name = params["method_name"].as_str().unwrap_or_default();
+                if method_name != "get_policy" {
+                    return Err(handler_error(format!("MethodNotFound: {method_name}")));
+                }
+                let policy = json!({
+                    "proposal_bond": self.daos[account_id].proposal_bond.to_string(),
+                });
+                Ok(json!({
+                    "result": serde_json::to_vec(&policy).unwrap(),
+                    "logs": [],
+                    "block_height": block_height,
+                    "block_hash": BLOCK_HASH,
+                }))
+            }
+            "call_function" => {
+                if account_id != self.contract_account_id {
+                    return Err(handler_error(format!(
+                        "Contract {account_id} is not deployed"
+                    )));
+                }
+                let args = BASE64_STANDARD
+                    .decode(params["args_base64"].as_str().unwrap_or_default())
+                    .ok()
+                    .and_then(|args| serde_json::from_slice::<Value>(&args).ok())
+                    .unwrap_or(Value::Null);
+                let method_name = params["method_name"].as_str().unwrap_or_default();
+                let result = self
+                    .call_view_function(method_name, &args)
+                    .map_err(handler_error)?;
+                Ok(json!({
+                    "result": serde_json::to_vec(&result).unwrap(),
+                    "logs": [],
+                    "block_height": block_height,
+                    "block_hash": BLOCK_HASH,
+                }))
+            }
+            "view_access_key" => {
+                let public_key = params["public_key"].as_str().unwrap_or_default();
+                let access_key = (account_id.to_owned(), public_key.to_owned());
+                let nonce = self.nonces.get(&access_key).copied().unwrap_or_default();
+                let permission = self
+                    .access_keys
+                    .get(&access_key)
+                    .cloned()
+                    .unwrap_or_else(|| json!("FullAccess"));
+                Ok(json!({
+                    "nonce": nonce,
+                    "permission": permission,
+                    "block_height": block_height,
+                    "block_hash": BLOCK_HASH,
+                }))
+            }
+            "view_access_key_list" => {
+                let mut keys = self
+                    .access_keys
+                    .iter()
+                    .filter(|((key_account_id, _), _)| key_account_id == account_id)
+                    .map(|((_, public_key), permission)| {
+                        json!({
+                            "public_key": public_key,
+                            "access_key": { "nonce": 0, "permission": permission },
+                        })
+                    })
+                    .collect::<Vec<_>>();
+                keys.extend(
+                    self.nonces
+                        .iter()
+                        .filter(|(access_key, _)| {
+                            access_key.0 == account_id
+                                && !self.access_keys.contains_key(*access_key)
+                        })
+                        .map(|((_, public_key), nonce)| {
+                            json!({
+                                "public_key": public_key,
+                                "access_key": { "nonce": nonce, "permission": "FullAccess" },
+                            })
+                        }),
+                );
+                Ok(json!({
+                    "keys": keys,
+                    "block_height": block_height,
+                    "block_hash": BLOCK_HASH,
+                }))
+            }
+            "view_account" => Ok(json!({
+                "amount": "100000000000000000000000000",
+                "locked": "0",
+                "code_hash": "11111111111111111111111111111111",
+                "storage_usage": 182,
+                "storage_paid_at": 0,
+                "block_height": block_height,
+                "block_hash": BLOCK_HASH,
+            })),
+            request_type => Err(handler_error(format!(
+                "The `{request_type}` query is not supported by the emulator"
+            ))),
+        }
+    }
+
+    fn handle_broadcast_tx_commit(
+        &mut self,
+        signed_transaction: Option<&str>,
+    ) -> Result<Value, Value> {
+        let signed_transaction = signed_transaction
+            .and_then(|signed_transaction| BASE64_STANDARD.decode(signed_transaction).ok())
+            .and_then(|bytes| {
+                near_primitives::transaction::SignedTransaction::try_from_slice(&bytes).ok()
+            })
+            .ok_or_else(|| handler_error("Failed to decode the signed transaction".to_owned()))?;
+        Ok(self.execute(signed_transaction))
+    }
+
+    /// Wraps the signed delegate action into a transaction signed by `relayer.near`.
+    fn handle_relay(&mut self, request: &Value) -> Result<Value, String> {
+        let signed_delegate_action = request["signed_delegate_action"]
+            .as_str()
+            .and_then(|signed_delegate_action| BASE64_STANDARD.decode(signed_delegate_action).ok())
+            .and_then(|bytes| {
+                near_primitives::action::delegate::SignedDelegateAction::try_from_slice(&bytes).ok()
+            })
+            .ok_or_else(|| "Failed to decode the signed delegate action".to_owned())?;
+        if !signed_delegate_action.verify() {
+            return Err("Invalid delegate action signature".to_owned());
+        }
+        let transaction = near_primitives::transaction::TransactionV0 {
+            signer_id: "relayer.near".parse().unwrap(),
+            public_key: signed_delegate_action.delegate_action.public_key.clone(),
+            nonce: 1,
+            receiver_id: signed_delegate_action.delegate_action.sender_id.clone(),
+            block_hash: BLOCK_HASH.parse().unwrap(),
+            actions: vec![near_primitives::transaction::Action::Delegate(Box::new(
+                signed_delegate_action,
+            ))],
+        };
+        Ok(
+            self.execute(near_primitives::transaction::SignedTransaction::new(
+                near_crypto::Signature::empty(near_crypto::KeyType::ED25519),
+                near_primitives::transaction::Transaction::V0(transaction),
+            )),
+        )
+    }
+
+    fn execute(
+        &mut self,
+        signed_transaction: near_primitives::transaction::SignedTransaction,
+    ) -> Value {
+        let transaction = &signed_transaction.transaction;
+        // A relayed delegate action is executed on behalf of its sender
+        let (signer_id, public_key, receiver_id, nonce, actions) = match transaction.actions() {
+            [near_primitives::transaction::Action::Delegate(signed_delegate_action)] => {
+                let delegate_action = &signed_delegate_action.delegate_action;
+                (
+                    delegate_action.sender_id.to_string(),
+                    delegate_action.public_key.to_string(),
+                    delegate_action.receiver_id.to_string(),
+                    delegate_action.nonce,
+                    delegate_action.get_actions(),
+                )
+            }
+            actions => (
+                transaction.signer_id().to_string(),
+                transaction.public_key().to_string(),
+                transaction.receiver_id().to_string(),
+                transaction.nonce(),
+                actions.to_vec(),
+            ),
+        };
+        self.nonces
+            .insert((signer_id.clone(), public_key.clone()), nonce);
+
+        let mut status = json!({ "SuccessValue": "" });
+        for action in &actions {
+            match action {
+                near_primitives::transaction::Action::AddKey(add_key) => {
+                    let permission = serde_json::to_value(
+                        near_primitives::views::AccessKeyPermissionView::from(
+                            add_key.access_key.permission.clone(),
+                        ),
+                    )
+                    .unwrap();
+                    self.access_keys.insert(
+                        (receiver_id.clone(), add_key.public_key.to_string()),
+                        permission,
+                    );
+                }
+                near_primitives::transaction::Action::DeleteKey(delete_key) => {
+                    self.access_keys
+                        .remove(&(receiver_id.clone(), delete_key.public_key.to_string()));
+                }
+                // Any deployed code is treated as the SocialDB contract
+                near_primitives::transaction::Action::DeployContract(_) => {
+                    self.contract_account_id = receiver_id.clone();
+                }
+                _ => {}
+            }
+        }
+        if let Some(dao) = self.daos.get_mut(&receiver_id) {
+            for action in &actions {
+                let near_primitives::transaction::Action::FunctionCall(function_call) = action
+                else {
+                    continue;
+                };
+                let args =
+                    serde_json::from_slice::<Value>(&function_call.args).unwrap_or(Value::Null);
+                if function_call.method_name == "add_proposal"
+                    && function_call.deposit >= dao.proposal_bond
+                {
+                    dao.proposals.push(args["proposal"].clone());
+                    let proposal_id = (dao.proposals.len() - 1).to_string();
+                    status = json!({ "SuccessValue": BASE64_STANDARD.encode(proposal_id) });
+                } else {
+                    status = json!({
+                        "Failure": {
+                            "ActionError": {
+                                "index": 0,
+                                "kind": { "FunctionCallError": { "ExecutionError": "ERR_MIN_BOND" } },
+                            }
+                        }
+                    });
+                }
+            }
+        } else if receiver_id == self.contract_account_id {
+            for (index, action) in actions.iter().enumerate() {
+                let near_primitives::transaction::Action::FunctionCall(function_call) = action
+                else {
+                    continue;
+                };
+                let args =
+                    serde_json::from_slice::<Value>(&function_call.args).unwrap_or(Value::Null);
+                if let Err(error) = self.call_function(
+                    &signer_id,
+                    &public_key,
+                    &function_call.method_name,
+                    &args,
+                    function_call.deposit,
+                ) {
+                    status = json!({
+                        "Failure": {
+                            "ActionError": {
+                                "index": index,
+                                "kind": { "FunctionCallError": { "ExecutionError": error } },
+                            }
+                        }
+                    });
+                    break;
+                }
+            }
+        }
+
+        let transaction_view = serde_json::to_value(
+            near_primitives::views::SignedTransactionView::from(signed_transaction.clone()),
+        )
+        .unwrap();
+        self.transactions.push(transaction_view.clone());
+        let transaction_hash = transaction_view["hash"].clone();
+
+        json!({
+            "final_execution_status": "FINAL",
+            "status": status,
+            "transaction": transaction_view,
+            "transaction_outcome": {
+                "proof": [],
+                "block_hash": BLOCK_HASH,
+                "id": transaction_hash,
+                "outcome": {
+                    "logs": [],
+                    "receipt_ids": [transaction_hash],
+                    "gas_burnt": 1,
+                    "tokens_burnt": "0",
+                    "executor_id": signer_id,
+                    "status": { "SuccessReceiptId": transaction_hash },
+                },
+            },
+            "receipts_outcome": [{
+                "proof": [],
+                "block_hash": BLOCK_HASH,
+                "id": transaction_hash,
+                "outcome": {
+                    "logs": [],
+                    "receipt_ids": [],
+                    "gas_burnt": 1,
+                    "tokens_burnt": "0",
+                    "executor_id": receiver_id,
+                    "status": status,
+                },
+            }],
+        })
+    }
+}
+
+/// A SocialDB tree node: the node value (stored under the `""` key in the `set` format) and the child nodes.
+#[derive(Debug, Default)]
+struct Node {
+    value: Option<Value>,
+    block_height: u64,
+    node_id: u64,
+    children: BTreeMap<String, Node>,
+}
+
+impl Node {
+    fn is_empty(&self) -> bool {
+        self.value.is_none() && self.children.is_empty()
+    }
+
+    fn set(&mut self, data: &Value, block_height: u64, next_node_id: &mut u64, keep_nulls: bool) {
+        self.block_height = block_height;
+        match data {
+            Value::Object(data) => {
+                for (key, value) in data {
+                    if key.is_empty() {
+                        self.set_value(value, keep_nulls);
+                        continue;
+                    }
+                    let child = self.children.entry(key.clone()).or_insert_with(|| {
+                        *next_node_id += 1;
+                        Node {
+                            node_id: *next_node_id,
+                            ..Default::default()
+                        }
+                    });
+                    child.set(value, block_height, next_node_id, keep_nulls);
+                    if child.is_empty() {
+                        self.children.remove(key);
+                    }
+                }
+            }
+            value => self.set_value(value, keep_nulls),
+        }
+    }
+
+    fn set_value(&mut self, value: &Value, keep_nulls: bool) {
+        self.value = match value {
+            Value::Null if !keep_nulls => None,
+            value => Some(value.clone()),
+        };
+    }
+
+    fn used_bytes(&self, key: &str) -> u64 {
+        let value_bytes = self
+            .value
+            .as_ref()
+            .map(|value| match value {
+                Value::String(value) => value.len(),
+                value => value.to_string().len(),
+            })
+            .unwrap_or_default();
+        (key.len() + value_bytes) as u64
+            + self
+                .children
+                .iter()
+                .map(|(key, child)| child.used_bytes(key))
+                .sum::<u64>()
+    }
+
+    fn render_value(&self, with_block_height: bool) -> Option<Value> {
+        let value = self.value.clone()?;
+        Some(if with_block_height {
+            json!({ "": value, ":block": self.block_height })
+        } else {
+            value
+        })
+    }
+
+    /// Renders the node value the way `get` returns it: plain leaves, and `""` for nodes with children.
+    fn render_get(&self, with_block_height: bool) -> Option<Value> {
+        if self.children.is_empty() {
+            self.render_value(with_block_height)
+        } else {
+            let value = self.render_value(with_block_height)?;
+            Some(json!({ "": value }))
+        }
+    }
+
+    fn render_subtree(&self, with_block_height: bool) -> Option<Value> {
+        if self.children.is_empty() {
+            return self.render_value(with_block_height);
+        }
+        let mut object = Map::new();
+        if let Some(value) = self.render_value(with_block_height) {
+            object.insert(String::new(), value);
+        }
+        for (key, child) in &self.children {
+            if let Some(value) = child.render_subtree(with_block_height) {
+                object.insert(key.clone(), value);
+            }
+        }
+        (!object.is_empty()).then_some(Value::Object(object))
+    }
+
+    fn key_marker(&self, return_type: &str) -> Value {
+        match return_type {
+            "BlockHeight" => json!(self.block_height),
+            "NodeId" => json!(self.node_id),
+            _ => json!(true),
+        }
+    }
+
+    fn render_keys_subtree(&self, return_type: &str, values_only: bool) -> Option<Value> {
+        if self.children.is_empty() {
+            return (!values_only || self.value.is_some()).then(|| self.key_marker(return_type));
+        }
+        let object = self
+            .children
+            .iter()
+            .filter_map(|(key, child)| {
+                child
+                    .render_keys_subtree(return_type, values_only)
+                    .map(|value| (key.clone(), value))
+            })
+            .collect::<Map<_, _>>();
+        (!object.is_empty()).then_some(Value::Object(object))
+    }
+
+    fn get(&self, keys: &[String], with_block_height: bool) -> Value {
+        let mut result = Map::new();
+        for key in keys {
+            let segments = key.split('/').collect::<Vec<_>>();
+            self.collect(&segments, &mut result, &|node, is_subtree| {
+                if is_subtree {
+                    node.render_subtree(with_block_height)
+                } else {
+                    node.render_get(with_block_height)
+                }
+            });
+        }
+        Value::Object(result)
+    }
+
+    fn keys(&self, keys: &[String], return_type: &str, values_only: bool) -> Value {
+        let mut result = Map::new();
+        for key in keys {
+            let segments = key.split('/').collect::<Vec<_>>();
+            self.collect(&segments, &mut result, &|node, is_subtree| {
+                if is_subtree {
+                    node.render_keys_subtree(return_type, values_only)
+                } else {
+                    (!values_only || node.value.is_some()).then(|| node.key_marker(return_type))
+                }
+            });
+        }
+        Value::Object(result)
+    }
+
+    /// Walks the key pattern segments (`*` matches any single key, a trailing `**` matches the
+    /// whole subtree) and merges the rendered matches into `result`.
+    fn collect(
+        &self,
+        segments: &[&str],
+        result: &mut Map<String, Value>,
+        render: &dyn Fn(&Node, bool) -> Option<Value>,
+    ) {
+        let Some((segment, rest)) = segments.split_first() else {
+            return;
+        };
+        let children: Vec<(&String, &Node)> = if *segment == "*" || *segment == "**" {
+            self.children.iter().collect()
+        } else {
+            self.children.get_key_value(*segment).into_iter().collect()
+        };
+        for (key, child) in children {
+            let rendered = match rest {
+                [] if *segment == "**" => render(child, true),
+                [] => render(child, false),
+                ["**"] => render(child, true),
+                rest => {
+                    let mut nested = match result.remove(key) {
+                        Some(Value::Object(nested)) => nested,
+                        _ => Map::new(),
+                    };
+                    child.collect(rest, &mut nested, render);
+                    (!nested.is_empty()).then_some(Value::Object(nested))
+                }
+            };
+            if let Some(rendered) = rendered {
+                merge(result, key, rendered);
+            }
+        }
+    }
+}
+
+fn merge(result: &mut Map<String, Value>, key: &str, value: Value) {
+    match (result.get_mut(key), value) {
+        (Some(Value::Object(existing)), Value::Object(value)) => {
+            for (key, value) in value {
+                merge(existing, &key, value);
+            }
+        }
+        (_, value) => {
+            result.insert(key.to_owned(), value);
+        }
+    }
+}
+
+fn leaves(path: &str, data: &Value) -> Vec<(String, Value)> {
+    match data {
+        Value::Object(data) => data
+            .iter()
+            .flat_map(|(key, value)| {
+                let path = if key.is_empty() {
+                    path.to_owned()
+                } else {
+                    format!("{path}/{key}")
+                };
+                leaves(&path, value)
+            })
+            .collect(),
+        value => vec![(path.to_owned(), value.clone())],
+    }
+}
+
+fn string_list(value: &Value) -> Vec<String> {
+    value
+        .as_array()
+        .map(|values| {
+            values
+                .iter()
+                .filter_map(|value| value.as_str().map(str::to_owned))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn permission_key_from_args(args: &Value) -> Result<PermissionKey, String> {
+    if let Some(predecessor_id) = args["predecessor_id"].as_str() {
+        Ok(PermissionKey::PredecessorId(predecessor_id.to_owned()))
+    } else if let Some(public_key) = args["public_key"].as_str() {
+        Ok(PermissionKey::PublicKey(public_key.to_owned()))
+    } else {
+        Err("Either `predecessor_id` or `public_key` must be provided".to_owned())
+    }
+}
+
+fn handler_error(message: String) -> Value {
+    json!({
+        "name": "HANDLER_ERROR",
+        "cause": {
+            "name": "CONTRACT_EXECUTION_ERROR",
+            "info": { "vm_error": message.clone(), "block_height": 0, "block_hash": BLOCK_HASH },
+        },
+        "code": -32000,
+        "message": "Server error",
+        "data": message,
+    })
+}
+
+fn serve_connection(stream: TcpStream, state: Arc<Mutex<State>>) {
+    let mut writer = match stream.try_clone() {
+        Ok(writer) => writer,
+        Err(_) => return,
+    };
+    let mut reader = BufReader::new(stream);
+    loop {
+        let mut content_length = 0;
+        let mut request_line = String::new();
+        if reader.read_line(&mut request_line).unwrap_or(0) == 0 {
+            return;
+        }
+        loop {
+            let mut header = String::new();
+            if reader.read_line(&mut header).unwrap_or(0) == 0 {
+                return;
+            }
+            let header = header.trim_end();
+            if header.is_empty() {
+                break;
+            }
+            if let Some((name, value)) = header.split_once(':') {
+                if name.eq_ignore_ascii_case("content-length") {
+                    content_length = value.trim().parse().unwrap_or(0);
+                }
+            }
+        }
+        let mut body = vec![0; content_length];
+        if reader.read_exact(&mut body).is_err() {
+            return;
+        }
+
+        let is_relay_request = request_line.split_whitespace().nth(1) == Some("/relay");
+        let (status_line, response) = match serde_json::from_slice::<Value>(&body) {
+            Ok(request) if is_relay_request => match state.lock().unwrap().handle_relay(&request) {
+                Ok(outcome) => ("200 OK", outcome.to_string()),
+                Err(error) => ("400 Bad Request", error),
+            },
+            Ok(request) => {
+                let mut state = state.lock().unwrap();
+                if state.fail_requests_except_status && request["method"] != "status" {
+                    state.failed_requests += 1;
+                    ("503 Service Unavailable", String::new())
+                } else {
+                    ("200 OK", state.handle(&request).to_string())
+                }
+            }
+            Err(_) => (
+                "200 OK",
+                json!({ "jsonrpc": "2.0", "error": "Parse error", "id": null }).to_string(),
+            ),
+        };
+        if write!(
+            writer,
+            "HTTP/1.1 {status_line}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+            response.len(),
+            response
+        )
+        .is_err()
+        {
+            return;
+        }
+    }
+}
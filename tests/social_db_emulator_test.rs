@@ -0,0 +1,107 @@
+mod social_db_emulator;
+mod test_util;
+use social_db_emulator::SocialDbEmulator;
+use test_util::*;
+
+const SIGNER_ARGS: [&str; 5] = [
+    "sign-with-plaintext-private-key",
+    "--signer-public-key",
+    "ed25519:7fvCiaE4NTmhexo8fDoa3CFNupL6mvJmNjL1hydN65fm",
+    "--signer-private-key",
+    "ed25519:VzeoRptTNGWeXwq3JNLdo8XqoBKvjSXyV5VxjvxPyzsiGmwo2Vu6LTiBujoQSXYjF8khQS5r3SSQK3xV8uomjv7",
+];
+
+fn bos(args: &[&str]) -> assert_cmd::assert::Assert {
+    bos_command().args(args).assert()
+}
+
+#[test]
+fn test_bos_socialdb_data_set_view_delete_with_emulator() {
+    let emulator = SocialDbEmulator::start();
+    emulator.set(serde_json::json!({
+        "test.near": { "profile": { "description": "Hello" } },
+        "bob.near": { "widget": { "app": "code" } },
+    }));
+
+    let config_dir = dirs::config_dir().unwrap().join("near-cli");
+    let backup_path = setup_config(&config_dir, &emulator.url());
+
+    let view = || {
+        bos(&[
+            "social-db",
+            "data",
+            "view",
+            "test.near/profile/**",
+            "--format",
+            "flat",
+            "network-config",
+            "mainnet",
+            "now",
+        ])
+    };
+
+    let set_args = [
+        "social-db",
+        "data",
+        "set",
+        "test.near/profile/name",
+        "--yes",
+        "with-text",
+        "Alice",
+        "sign-as",
+        "test.near",
+        "network-config",
+        "mainnet",
+    ];
+    bos(&[&set_args[..], &SIGNER_ARGS[..], &["send"]].concat()).success();
+
+    view()
+        .success()
+        .stdout("test.near/profile/description = \"Hello\"\ntest.near/profile/name = \"Alice\"\n");
+
+    let delete_args = [
+        "social-db",
+        "data",
+        "delete",
+        "test.near/profile/name",
+        "sign-as",
+        "test.near",
+        "network-config",
+        "mainnet",
+    ];
+    bos(&[&delete_args[..], &SIGNER_ARGS[..], &["send"]].concat()).success();
+
+    view()
+        .success()
+        .stdout("test.near/profile/description = \"Hello\"\n");
+    let transactions_count = emulator.transactions().len();
+
+    // The emulator enforces the SocialDB write permissions
+    let unpermitted_set_args = [
+        "social-db",
+        "data",
+        "set",
+        "bob.near/widget/app",
+        "--yes",
+        "with-text",
+        "hacked",
+        "sign-as",
+        "test.near",
+        "network-config",
+        "mainnet",
+    ];
+    let unpermitted_set = bos(&[&unpermitted_set_args[..], &SIGNER_ARGS[..], &["send"]].concat());
+
+    restore_config(&config_dir, backup_path);
+
+    assert_eq!(transactions_count, 2);
+    assert_eq!(
+        emulator.get(&["test.near/profile/**"]),
+        serde_json::json!({ "test.near": { "profile": { "description": "Hello" } } })
+    );
+    unpermitted_set.failure();
+    assert_eq!(
+        emulator.get(&["bob.near/widget/app"]),
+        serde_json::json!({ "bob.near": { "widget": { "app": "code" } } })
+    );
+}
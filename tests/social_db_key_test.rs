@@ -0,0 +1,73 @@
+use bos_cli::common::social_db_key_for_signer;
+
+fn signer() -> near_primitives::types::AccountId {
+    "frol.near".parse().unwrap()
+}
+
+#[test]
+fn test_social_db_key_for_signer_prefixes_relative_keys() {
+    assert_eq!(
+        social_db_key_for_signer(&signer(), "widget").unwrap(),
+        "frol.near/widget"
+    );
+    assert_eq!(
+        social_db_key_for_signer(&signer(), "/widget/").unwrap(),
+        "frol.near/widget"
+    );
+    // `Widget` is not a valid account ID, so the key cannot be absolute
+    assert_eq!(
+        social_db_key_for_signer(&signer(), "Widget/Nav").unwrap(),
+        "frol.near/Widget/Nav"
+    );
+    assert!(social_db_key_for_signer(&signer(), "/").is_err());
+}
+
+#[test]
+fn test_social_db_key_for_signer_checks_absolute_keys() {
+    assert_eq!(
+        social_db_key_for_signer(&signer(), "frol.near/widget").unwrap(),
+        "frol.near/widget"
+    );
+    assert!(social_db_key_for_signer(&signer(), "bob.near/widget").is_err());
+}
+
+#[test]
+fn test_social_db_key_for_signer_checks_implicit_account_keys() {
+    let implicit_account_id = "98793cd91a3f870fb126f66285808c7e094afcfc4eda8a970f6648cdf0dbd6de";
+    assert!(social_db_key_for_signer(&signer(), &format!("{implicit_account_id}/widget")).is_err());
+    assert!(social_db_key_for_signer(&signer(), implicit_account_id).is_err());
+    assert!(social_db_key_for_signer(
+        &signer(),
+        "0xb794f5ea0ba39494ce839613fffba74279579268/widget"
+    )
+    .is_err());
+
+    let implicit_signer = implicit_account_id.parse().unwrap();
+    assert_eq!(
+        social_db_key_for_signer(&implicit_signer, &format!("{implicit_account_id}/widget"))
+            .unwrap(),
+        format!("{implicit_account_id}/widget")
+    );
+}
+
+#[test]
+fn test_social_db_key_for_signer_prefixes_keys_starting_with_top_level_account_names() {
+    assert_eq!(
+        social_db_key_for_signer(&signer(), "widget/Nav").unwrap(),
+        "frol.near/widget/Nav"
+    );
+    assert_eq!(
+        social_db_key_for_signer(&signer(), "index/notify").unwrap(),
+        "frol.near/index/notify"
+    );
+
+    let top_level_signer = "bob".parse().unwrap();
+    assert_eq!(
+        social_db_key_for_signer(&top_level_signer, "bob/widget").unwrap(),
+        "bob/widget"
+    );
+    assert_eq!(
+        social_db_key_for_signer(&top_level_signer, "widget").unwrap(),
+        "bob/widget"
+    );
+}
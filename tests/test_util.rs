@@ -1,3 +1,6 @@
+#![allow(dead_code)]
+
+use assert_cmd::Command;
 use base64::prelude::*;
 use httpmock::prelude::HttpMockRequest;
 use httpmock::{MockServer, Then, When};
@@ -25,6 +28,9 @@ pub fn setup_config(config_dir: &Path, server_url: &str) -> Option<std::path::Pa
         fs::rename(&config_path, &backup_path).expect("Failed to backup original config.toml");
     }
 
+    // Every test starts with an empty SocialDB response cache (see `bos_command`)
+    let _ = fs::remove_dir_all(test_cache_dir());
+
     fs::create_dir_all(config_dir).expect("Failed to create config directory");
     fs::write(
         &config_path,
@@ -63,6 +69,7 @@ pub fn setup_config(config_dir: &Path, server_url: &str) -> Option<std::path::Pa
 /// * `config_dir` - The path to the directory containing `config.toml`.
 /// * `backup_path` - The path to the backup `config.toml`, if it exists.
 pub fn restore_config(config_dir: &Path, backup_path: Option<std::path::PathBuf>) {
+    let _ = fs::remove_dir_all(test_cache_dir());
     let config_path = config_dir.join("config.toml");
     if let Some(backup_path) = backup_path {
         fs::rename(backup_path, config_path).expect("Failed to restore original config.toml");
@@ -71,6 +78,19 @@ pub fn restore_config(config_dir: &Path, backup_path: Option<std::path::PathBuf>
     }
 }
 
+/// The SocialDB response cache directory used by the `bos` commands run by the current test binary.
+pub fn test_cache_dir() -> std::path::PathBuf {
+    std::env::temp_dir().join(format!("bos-cli-test-cache-{}", std::process::id()))
+}
+
+/// The `bos` command, with the SocialDB response cache in [`test_cache_dir`] instead of the user
+/// cache directory.
+pub fn bos_command() -> Command {
+    let mut command = Command::cargo_bin("bos").unwrap();
+    command.env("BOS_CACHE_DIR", test_cache_dir());
+    command
+}
+
 /// Sets up a temporary directory for storing test components and returns a `TempDir` object.
 ///
 /// # Returns